@@ -0,0 +1,156 @@
+//! A global allocator that counts, installed with the `alloc-stats` feature.
+//!
+//! Without the feature the counters never move and [`ENABLED`] is false.
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::atomic::{AtomicUsize, Ordering::Relaxed},
+};
+
+use serde::Serialize;
+
+/// Forwards to the system allocator and counts what goes through it
+pub struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+pub const ENABLED: bool = cfg!(feature = "alloc-stats");
+
+fn allocated(size: usize) {
+    ALLOCATIONS.fetch_add(1, Relaxed);
+    ALLOCATED.fetch_add(size, Relaxed);
+    let live = LIVE.fetch_add(size, Relaxed) + size;
+    PEAK.fetch_max(live, Relaxed);
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size(), Relaxed);
+    }
+
+    // counted as a new allocation of the new size that frees the old one
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE.fetch_sub(layout.size(), Relaxed);
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+/// What some code allocated, from all threads
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Memory {
+    pub allocations: usize,
+    pub bytes: usize,
+    /// Most bytes live at the same time, on top of what was live before
+    pub peak: usize,
+}
+
+/// Runs f and counts its allocations. Not meant to be nested, the inner call resets the peak.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Memory) {
+    let allocations = ALLOCATIONS.load(Relaxed);
+    let bytes = ALLOCATED.load(Relaxed);
+    let live = LIVE.load(Relaxed);
+    PEAK.store(live, Relaxed);
+    let result = f();
+    let memory = Memory {
+        allocations: ALLOCATIONS.load(Relaxed) - allocations,
+        bytes: ALLOCATED.load(Relaxed) - bytes,
+        peak: PEAK.load(Relaxed).saturating_sub(live),
+    };
+    (result, memory)
+}
+
+impl Memory {
+    /// Average allocations and bytes of one run, the peak is already the highest of all runs
+    pub fn per_run(self, runs: usize) -> Memory {
+        let runs = runs.max(1);
+        Memory {
+            allocations: self.allocations / runs,
+            bytes: self.bytes / runs,
+            peak: self.peak,
+        }
+    }
+}
+
+// "1.50 MiB"
+fn bytes(n: usize) -> String {
+    let mut value = n as f64;
+    for unit in ["B", "KiB", "MiB"] {
+        if value < 1024.0 {
+            return if unit == "B" {
+                format!("{} {}", n, unit)
+            } else {
+                format!("{:.2} {}", value, unit)
+            };
+        }
+        value /= 1024.0;
+    }
+    format!("{:.2} GiB", value)
+}
+
+impl fmt::Display for Memory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {}, peak {}",
+            self.allocations,
+            bytes(self.bytes),
+            bytes(self.peak)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let memory = Memory {
+            allocations: 3,
+            bytes: 1536,
+            peak: 100,
+        };
+        assert_eq!(memory.to_string(), "3 allocations, 1.50 KiB, peak 100 B");
+        assert_eq!(bytes(3 << 30), "3.00 GiB");
+        assert_eq!(memory.per_run(2).allocations, 1);
+    }
+
+    #[cfg(feature = "alloc-stats")]
+    #[test]
+    fn test_measure() {
+        let (v, memory) = measure(|| vec![0u8; 4096]);
+        assert_eq!(v.len(), 4096);
+        assert!(memory.allocations >= 1);
+        // other tests allocate at the same time, so only the totals are reliable
+        assert!(memory.bytes >= 4096);
+    }
+}
@@ -0,0 +1,127 @@
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+use thiserror::Error;
+use toml::{Table, Value};
+
+use aoc24::solution::Answer;
+
+// Known answers of one input directory, stored as
+//
+// [day01]
+// part1 = 11
+// part2 = 31
+//
+// Answers are compared by their string representation, so it does not
+// matter whether an answer is stored as a number or a string.
+#[derive(Debug, Default)]
+pub struct Answers {
+    days: BTreeMap<String, [Option<String>; 2]>,
+}
+
+#[derive(Error, Debug)]
+pub enum AnswersError {
+    #[error("Cannot access answers file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Answers file is not valid toml: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Answers file has unexpected entry: {0}")]
+    WrongFormat(String),
+}
+
+impl Answers {
+    // a missing file is the same as an empty one
+    pub fn load(path: &Path) -> Result<Answers, AnswersError> {
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), AnswersError> {
+        Ok(fs::write(path, self.to_string())?)
+    }
+
+    // part is 1 or 2
+    pub fn get(&self, label: &str, part: usize) -> Option<&str> {
+        self.days.get(label)?[part - 1].as_deref()
+    }
+
+    pub fn set(&mut self, label: &str, part: usize, answer: &Answer) {
+        self.days.entry(label.to_owned()).or_default()[part - 1] = Some(answer.to_string());
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = AnswersError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+        for (label, parts) in s.parse::<Table>()? {
+            let Value::Table(parts) = parts else {
+                return Err(AnswersError::WrongFormat(label));
+            };
+            let day = answers.days.entry(label.clone()).or_default();
+            for (part, value) in parts {
+                let index = match part.as_str() {
+                    "part1" => 0,
+                    "part2" => 1,
+                    _ => return Err(AnswersError::WrongFormat(format!("{}.{}", label, part))),
+                };
+                day[index] = Some(match value {
+                    Value::Integer(n) => n.to_string(),
+                    Value::String(s) => s,
+                    _ => return Err(AnswersError::WrongFormat(format!("{}.{}", label, part))),
+                });
+            }
+        }
+        Ok(answers)
+    }
+}
+
+impl std::fmt::Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut table = Table::new();
+        for (label, parts) in &self.days {
+            let mut day = Table::new();
+            for (name, answer) in ["part1", "part2"].iter().zip(parts) {
+                if let Some(answer) = answer {
+                    // numbers are nicer to read, but toml only knows i64
+                    let value = answer
+                        .parse::<i64>()
+                        .map(Value::Integer)
+                        .unwrap_or_else(|_| Value::String(answer.clone()));
+                    day.insert(name.to_string(), value);
+                }
+            }
+            table.insert(label.clone(), Value::Table(day));
+        }
+        write!(f, "{}", table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let mut answers = Answers::default();
+        answers.set("day01", 1, &Answer::from(11usize));
+        answers.set("day01", 2, &Answer::from(u64::MAX));
+        answers.set("day17", 1, &Answer::from("7,4,2"));
+
+        let parsed: Answers = answers.to_string().parse().unwrap();
+        assert_eq!(parsed.get("day01", 1), Some("11"));
+        assert_eq!(parsed.get("day01", 2), Some(u64::MAX.to_string().as_str()));
+        assert_eq!(parsed.get("day17", 1), Some("7,4,2"));
+        assert_eq!(parsed.get("day17", 2), None);
+        assert_eq!(parsed.get("day02", 1), None);
+    }
+
+    #[test]
+    fn test_unknown_part() {
+        let parsed = "[day01]\npart3 = 1\n".parse::<Answers>();
+        assert!(matches!(parsed, Err(AnswersError::WrongFormat(_))));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use serde::Deserialize;
+
+use aoc24::params::ParamValue;
+
+use crate::serve::{self, Query, Service};
+
+// One request per line, e.g. {"day": 14, "input": "p=0,4 v=3,-3\n", "part": 1, "params": {"width": 11}},
+// answered with one line each in the same order, like the responses of `serve`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    day: u8,
+    input: String,
+    #[serde(default)]
+    part: Option<u8>,
+    #[serde(default)]
+    variant: Option<String>,
+    #[serde(default)]
+    params: BTreeMap<String, ParamValue>,
+}
+
+impl Request {
+    fn query(&self) -> Query {
+        Query {
+            part: self.part,
+            variant: self.variant.clone(),
+            params: self
+                .params
+                .iter()
+                .map(|(name, value)| (name.clone(), value.to_string()))
+                .collect(),
+        }
+    }
+}
+
+// answers until input ends, a line that is not a valid request, not even utf-8, or whose
+// solution panics gets {"error": ...} and the next line is read
+pub fn run(service: &Service, input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    for line in input.split(b'\n') {
+        let line = String::from_utf8(line?);
+        if line.as_ref().is_ok_and(|l| l.trim().is_empty()) {
+            continue;
+        }
+        let (_, response) = match line.map(|l| serde_json::from_str::<Request>(&l)) {
+            Ok(Ok(request)) => service.solve(request.day, &request.query(), &request.input),
+            Ok(Err(e)) => serve::error(400, format!("Bad request: {}", e)),
+            Err(e) => serve::error(400, format!("Bad request: {}", e)),
+        };
+        writeln!(output, "{}", response)?;
+        // the caller may wait for the answer before it sends the next request
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc24::bench::{BenchConfig, Until};
+    use serde_json::Value;
+
+    use super::*;
+
+    #[test]
+    fn test_run() {
+        let service = Service {
+            config: BenchConfig {
+                warmup: 0,
+                until: Until::Iterations(1),
+            },
+            budgets: vec![],
+            workers: aoc24::runner::Workers::new(1),
+        };
+        let input = [
+            r#"{"day": 11, "input": "125 17", "part": 1}"#,
+            "",
+            "not json",
+            r#"{"day": 11, "input": "125 17", "params": {"blinks1": 6, "blinks2": [1]}}"#,
+            r#"{"day": 11, "input": "125 17", "parts": 1}"#,
+            r#"{"day": 26, "input": ""}"#,
+            // used to panic in part 2
+            r#"{"day": 4, "input": "x"}"#,
+        ]
+        .join("\n");
+        // a line that is not utf-8 doesn't end the batch
+        let input = [
+            input.as_bytes(),
+            b"\n\xff\xfe\n",
+            br#"{"day": 11, "input": "125 17", "part": 1}"#,
+        ]
+        .concat();
+        let mut output = Vec::new();
+        run(&service, &input[..], &mut output).unwrap();
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 8);
+        assert_eq!(lines[0]["part1"], "55312");
+        assert_eq!(lines[0]["part2"], Value::Null);
+        assert!(lines[1]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Bad request"));
+        assert_eq!(
+            (&lines[2]["part1"], &lines[2]["part2"]),
+            (&"22".into(), &"3".into())
+        );
+        assert!(lines[3]["error"].as_str().unwrap().contains("parts"));
+        assert!(lines[4]["error"].is_string());
+        assert!(lines[5]["error"].is_string());
+        assert!(lines[6]["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Bad request"));
+        assert_eq!(lines[7]["part1"], "55312");
+    }
+}
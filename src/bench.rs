@@ -0,0 +1,178 @@
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Until {
+    Iterations(usize),
+    // keep going until this much time has been spent (but run at least once)
+    WallTime(Duration),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchConfig {
+    pub warmup: usize,
+    pub until: Until,
+}
+
+/// Runs f according to config, returns the last result and the duration of every timed run.
+/// Warm-up runs are not timed, but their errors are reported.
+pub fn run<F, R, E>(config: &BenchConfig, mut f: F) -> Result<(R, Vec<Duration>), E>
+where
+    F: FnMut() -> Result<R, E>,
+{
+    for _ in 0..config.warmup {
+        f()?;
+    }
+
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    loop {
+        let before = Instant::now();
+        let result = f()?;
+        samples.push(before.elapsed());
+        let done = match config.until {
+            Until::Iterations(n) => samples.len() >= n,
+            Until::WallTime(d) => start.elapsed() >= d,
+        };
+        if done {
+            return Ok((result, samples));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    // outside of the Tukey fences (1.5 interquartile ranges away from the quartiles)
+    pub outliers: usize,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        let mut secs: Vec<f64> = samples.iter().map(Duration::as_secs_f64).collect();
+        secs.sort_by(f64::total_cmp);
+
+        let n = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / n;
+        // sample standard deviation, 0 if there's only one sample
+        let variance = secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0).max(1.0);
+
+        let (q1, q3) = (quantile(&secs, 0.25), quantile(&secs, 0.75));
+        let fence = 1.5 * (q3 - q1);
+        let outliers = secs
+            .iter()
+            .filter(|&&s| s < q1 - fence || s > q3 + fence)
+            .count();
+
+        Stats {
+            samples: secs.len(),
+            min: Duration::from_secs_f64(secs[0]),
+            median: Duration::from_secs_f64(quantile(&secs, 0.5)),
+            mean: Duration::from_secs_f64(mean),
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            outliers,
+        }
+    }
+}
+
+// linear interpolation between the closest ranks, sorted must be sorted and non-empty
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let rank = q * (sorted.len() - 1) as f64;
+    let (below, above) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[below] + (sorted[above] - sorted[below]) * (rank - below as f64)
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:.2?}, median {:.2?}, mean {:.2?}, stddev {:.2?}, outliers {}/{}",
+            self.min, self.median, self.mean, self.stddev, self.outliers, self.samples
+        )
+    }
+}
+
+/// "1.5s", "200ms", "50us" or plain seconds
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let (number, unit) = s
+        .find(|c: char| c.is_ascii_alphabetic())
+        .map_or((s, "s"), |i| s.split_at(i));
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("not a duration: {}", s))?;
+    let scale = match unit {
+        "s" => 1.0,
+        "ms" => 1e-3,
+        "us" | "µs" => 1e-6,
+        _ => return Err(format!("unknown unit: {}", unit)),
+    };
+    Duration::try_from_secs_f64(number * scale).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|&m| Duration::from_millis(m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats::new(&ms(&[4, 2, 3, 1, 5]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        // sqrt(10 / 4)
+        assert_eq!(stats.stddev.as_micros(), 1581);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn test_single_sample() {
+        let stats = Stats::new(&ms(&[7]));
+        assert_eq!(stats.median, Duration::from_millis(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn test_outliers() {
+        let stats = Stats::new(&ms(&[10, 11, 10, 12, 11, 10, 100]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_millis(11));
+    }
+
+    #[test]
+    fn test_run_iterations() {
+        let mut calls = 0;
+        let config = BenchConfig {
+            warmup: 2,
+            until: Until::Iterations(3),
+        };
+        let (last, samples) = run(&config, || -> Result<_, ()> {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!((last, samples.len()), (5, 3));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("2"), Ok(Duration::from_secs(2)));
+        assert_eq!(parse_duration("1.5s"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_duration("200ms"), Ok(Duration::from_millis(200)));
+        assert_eq!(parse_duration("50us"), Ok(Duration::from_micros(50)));
+        assert!(parse_duration("3h").is_err());
+        assert!(parse_duration("fast").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::Path,
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use aoc24::{params::Params, solution::Answer};
+
+// Answers of earlier runs, so that slow days are not solved again for the same input.
+// Entries are keyed by the label of the variant, its parameters and a hash of the input.
+// The hash is only stable for one build, so a cache written by another build is dropped as a whole.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Cache {
+    build: String,
+    answers: BTreeMap<String, [Option<String>; 2]>,
+}
+
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Cannot access answer cache: {0}")]
+    Io(#[from] io::Error),
+    #[error("Answer cache is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+// the crate version and when the binary was built
+fn build_id() -> String {
+    let built = env::current_exe()
+        .and_then(fs::metadata)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+    format!("{}-{}", env!("CARGO_PKG_VERSION"), built)
+}
+
+pub fn key(label: &str, params: &Params, input: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    format!("{} {:016x} {}", label, hasher.finish(), params)
+        .trim_end()
+        .to_owned()
+}
+
+impl Cache {
+    pub fn new() -> Cache {
+        Cache {
+            build: build_id(),
+            answers: BTreeMap::new(),
+        }
+    }
+
+    // a missing file or one of another build is the same as an empty one
+    pub fn load(path: &Path) -> Result<Cache, CacheError> {
+        let empty = Cache::new();
+        let cache: Cache = match fs::read_to_string(path) {
+            Ok(s) => serde_json::from_str(&s)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(empty),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(if cache.build == empty.build {
+            cache
+        } else {
+            empty
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), CacheError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    pub fn clear(path: &Path) -> Result<(), CacheError> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    // only a hit if all asked for parts are known, parts that were not asked for are None
+    pub fn get(&self, key: &str, parts: &[usize]) -> Option<(Option<Answer>, Option<Answer>)> {
+        let known = self.answers.get(key)?;
+        let answer = |part: usize| -> Option<Option<Answer>> {
+            if parts.contains(&part) {
+                known[part - 1].as_deref().map(|a| Some(Answer::from(a)))
+            } else {
+                Some(None)
+            }
+        };
+        Some((answer(1)?, answer(2)?))
+    }
+
+    pub fn set(&mut self, key: String, answers: &(Option<Answer>, Option<Answer>)) {
+        let known = self.answers.entry(key).or_default();
+        for (known, answer) in known.iter_mut().zip([&answers.0, &answers.1]) {
+            if let Some(answer) = answer {
+                *known = Some(answer.to_string());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_and_set() {
+        let mut cache = Cache::new();
+        let key = key("day06", &Params::default(), "..#\n");
+        assert_ne!(key, super::key("day06", &Params::default(), "#..\n"));
+
+        cache.set(key.clone(), &(Some(Answer::from(41usize)), None));
+        assert_eq!(
+            cache.get(&key, &[1]),
+            Some((Some(Answer::from("41")), None))
+        );
+        assert_eq!(cache.get(&key, &[1, 2]), None);
+
+        cache.set(key.clone(), &(None, Some(Answer::from(6usize))));
+        let both = cache.get(&key, &[1, 2]).unwrap();
+        assert_eq!(
+            (both.0.unwrap().to_string(), both.1.unwrap().to_string()),
+            ("41".into(), "6".into())
+        );
+    }
+}
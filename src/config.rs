@@ -0,0 +1,230 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use serde::Deserialize;
+use thiserror::Error;
+
+use aoc24::{
+    params::{ParamError, ParamValue, Params},
+    registry, runner,
+};
+
+use crate::report::Format;
+
+// Defaults for the command line of a project, read from aoc.toml in the working directory
+// or the file given with --config, e.g.
+//
+// input_base = "inputs/alice"
+// iterations = 10
+// days = "1-5,7"
+// format = "json"
+// budget = ["2s", "6=30s"]
+//
+// [params.day14]
+// width = 11
+//
+// Options given on the command line win, budgets and parameters of the command line are
+// applied after the ones of the file. Unknown keys are an error, not silently ignored.
+// days select what is solved, generate still needs its day from --day.
+pub const DEFAULT_PATH: &str = "aoc.toml";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Config {
+    pub input_base: Option<String>,
+    pub iterations: Option<usize>,
+    pub days: Vec<RangeInclusive<u8>>,
+    pub format: Option<Format>,
+    pub budget: Vec<(Option<RangeInclusive<u8>>, Duration)>,
+    // by day, like --param but only for that day
+    pub params: BTreeMap<u8, Vec<(String, String)>>,
+}
+
+// the file as written, checked and converted by Config::from_str
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    input_base: Option<String>,
+    iterations: Option<usize>,
+    days: Option<String>,
+    format: Option<String>,
+    #[serde(default)]
+    budget: Vec<String>,
+    #[serde(default)]
+    params: BTreeMap<String, BTreeMap<String, ParamValue>>,
+}
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Cannot read config file {0}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("Config file is not valid: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("Config file has a bad value for {0}: {1}")]
+    Invalid(String, String),
+}
+
+impl Config {
+    // path is from --config and needs to exist, aoc.toml is optional
+    pub fn load(path: Option<&Path>) -> Result<Config, ConfigError> {
+        let (path, required) = match path {
+            Some(path) => (path, true),
+            None => (Path::new(DEFAULT_PATH), false),
+        };
+        match fs::read_to_string(path) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => Ok(Config::default()),
+            Err(e) => Err(ConfigError::Io(path.to_owned(), e)),
+        }
+    }
+}
+
+// every parameter needs to be declared by a variant of its day, and be valid for all of them
+fn day_params(
+    label: &str,
+    values: &BTreeMap<String, ParamValue>,
+) -> Result<(u8, Vec<(String, String)>), ConfigError> {
+    let invalid =
+        |e: &dyn ToString| ConfigError::Invalid(format!("params.{}", label), e.to_string());
+    let day = registry::DAYS
+        .iter()
+        .find(|d| d.label == label)
+        .ok_or_else(|| invalid(&"there is no such day"))?;
+    let params = values
+        .iter()
+        .map(|(name, value)| (name.clone(), value.to_string()))
+        .collect_vec();
+    for (name, value) in &params {
+        let declaring = day
+            .variants
+            .iter()
+            .map(|v| v.solution.params())
+            .filter(|declared| declared.iter().any(|p| p.name == name))
+            .collect_vec();
+        if declaring.is_empty() {
+            return Err(invalid(&ParamError::Unknown {
+                name: name.clone(),
+                known: day
+                    .variants
+                    .iter()
+                    .flat_map(|v| v.solution.params())
+                    .map(|p| p.name)
+                    .unique()
+                    .join(", "),
+            }));
+        }
+        for declared in declaring {
+            Params::resolve(declared, &[(name.clone(), value.clone())]).map_err(|e| invalid(&e))?;
+        }
+    }
+    Ok((day.day, params))
+}
+
+impl std::str::FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: File = toml::from_str(s)?;
+        let invalid =
+            |key: &str, e: &dyn ToString| ConfigError::Invalid(key.to_owned(), e.to_string());
+        Ok(Config {
+            input_base: file.input_base,
+            iterations: file.iterations,
+            days: match &file.days {
+                Some(days) => days
+                    .split(',')
+                    .map(|d| runner::parse_day_range(d.trim()))
+                    .try_collect()
+                    .map_err(|e| invalid("days", &e))?,
+                None => Vec::new(),
+            },
+            format: file
+                .format
+                .map(|f| Format::from_str(&f, true))
+                .transpose()
+                .map_err(|e| invalid("format", &e))?,
+            budget: file
+                .budget
+                .iter()
+                .map(|b| runner::parse_budget(b))
+                .try_collect()
+                .map_err(|e| invalid("budget", &e))?,
+            params: file
+                .params
+                .iter()
+                .map(|(label, values)| day_params(label, values))
+                .try_collect()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config: Config = r#"
+            input_base = "inputs/alice"
+            iterations = 10
+            days = "1-5, 7"
+            format = "json"
+            budget = ["2s", "6=30s"]
+
+            [params.day14]
+            width = 11
+            height = "7"
+
+            [params.day17]
+            program = [2, 4, 1]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(config.input_base.as_deref(), Some("inputs/alice"));
+        assert_eq!(config.iterations, Some(10));
+        assert_eq!(config.days, vec![1..=5, 7..=7]);
+        assert_eq!(config.format, Some(Format::Json));
+        assert_eq!(
+            config.budget,
+            vec![
+                (None, Duration::from_secs(2)),
+                (Some(6..=6), Duration::from_secs(30))
+            ]
+        );
+        assert_eq!(
+            config.params[&14],
+            vec![
+                ("height".to_owned(), "7".to_owned()),
+                ("width".to_owned(), "11".to_owned())
+            ]
+        );
+        assert_eq!(
+            config.params[&17],
+            vec![("program".to_owned(), "2,4,1".to_owned())]
+        );
+        assert_eq!("".parse::<Config>().unwrap(), Config::default());
+    }
+
+    #[test]
+    fn test_invalid() {
+        for toml in [
+            "input = \"x\"",
+            "iterations = \"many\"",
+            "days = \"1-\"",
+            "format = \"yaml\"",
+            "budget = [\"forever\"]",
+            "[params.day99]\nwidth = 11",
+            "[params.day14]\nblinks1 = 3",
+            "[params.day14]\nwidth = 0",
+            "[days]",
+        ] {
+            assert!(toml.parse::<Config>().is_err(), "{}", toml);
+        }
+    }
+}
@@ -1,32 +1,74 @@
 use std::collections::HashMap;
 use std::iter::zip;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (mut left, mut right) = parse_input(&input);
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    left.sort();
-    right.sort();
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<u32>, Vec<u32>);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse_input(input)
+    }
 
-    (part1(&left, &right) as usize, part2(&left, &right) as usize)
+    fn part1(&self, (left, right): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(left, right).into())
+    }
+
+    fn part2(&self, (left, right): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(left, right).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
 }
 
-fn parse_input(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input
+// two columns of five digit numbers, some of the right ones repeat left ones for part 2
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let left: Vec<_> = (0..size).map(|_| rng.between(10000, 99999)).collect();
+    left.iter()
+        .map(|l| {
+            let r = if rng.chance(0.3) {
+                rng.pick(&left)
+            } else {
+                rng.between(10000, 99999)
+            };
+            format!("{}   {}\n", l, r)
+        })
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<(Vec<u32>, Vec<u32>), DayError> {
+    let (mut left, mut right): (Vec<u32>, Vec<u32>) = input
         .lines()
         .map(|s| {
             s.split_whitespace()
-                .map(|s| s.parse::<u32>().expect("Failed to parse input"))
-                .collect()
+                .map(|s| s.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map(|ints| match ints?[..] {
+            [l, r] => Ok((l, r)),
+            _ => Err(DayError::WrongFormat("expected two numbers per line")),
         })
-        .map(|ints: Vec<u32>| -> (u32, u32) { (ints[0], ints[1]) })
-        .unzip()
+        .collect::<Result<_, DayError>>()?;
+
+    left.sort();
+    right.sort();
+
+    Ok((left, right))
 }
 
-fn part1(left: &Vec<u32>, right: &Vec<u32>) -> u32 {
+fn part1(left: &[u32], right: &[u32]) -> u32 {
     zip(left, right).map(|(l, r)| u32::abs_diff(*l, *r)).sum()
 }
 
-fn part2(left: &Vec<u32>, right: &Vec<u32>) -> u32 {
+fn part2(left: &[u32], right: &[u32]) -> u32 {
     let mut counter = HashMap::new();
 
     for element in right {
@@ -1,35 +1,82 @@
 use std::collections::HashMap;
 use std::hash::Hash;
+
+use itertools::Itertools;
+
 use crate::util::IteratorExt;
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, reports: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(reports).into())
+    }
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let input = parse_input(input);
-    (part1(&input), part2(&input))
+    fn part2(&self, reports: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(reports).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
-    input
-        .lines()
-        .map(|s| {
-            s.split_whitespace()
-                .map(|ss| ss.parse::<i32>().unwrap())
-                .collect()
+// reports that mostly step by 1 to 3 in one direction, some have bad levels
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let sign = if rng.chance(0.5) { 1 } else { -1 };
+            let mut level = rng.between(10, 90);
+            let mut levels = vec![level];
+            for _ in 1..rng.between(5, 8) {
+                let step = if rng.chance(0.1) {
+                    rng.between(-3, 5)
+                } else {
+                    rng.between(1, 3)
+                };
+                level += sign * step;
+                levels.push(level);
+            }
+            levels.iter().join(" ") + "\n"
         })
         .collect()
 }
 
-fn part1(reports: &Vec<Vec<i32>>) -> usize {
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, DayError> {
+    let reports: Vec<Vec<i32>> = input
+        .lines()
+        .map(|s| s.split_whitespace().map(|ss| ss.parse::<i32>()).collect())
+        .collect::<Result<_, _>>()?;
+    // is_safe_part2 needs a majority sign, i.e. at least one step
+    if reports.iter().any(|r| r.len() < 2) {
+        return Err(DayError::WrongFormat("reports need at least two levels"));
+    }
+    Ok(reports)
+}
+
+fn part1(reports: &[Vec<i32>]) -> usize {
     reports.iter().filter(|r| is_safe_part1(r)).count()
 }
 
-fn part2(reports: &Vec<Vec<i32>>) -> usize {
+fn part2(reports: &[Vec<i32>]) -> usize {
     reports
         .iter()
         .filter(|report| is_safe_part2(report.to_vec()))
         .count()
 }
 
-fn is_safe_part1(report: &Vec<i32>) -> bool {
+fn is_safe_part1(report: &[i32]) -> bool {
     // TODO: Is there a bird here?
     let steps_small_enough = report
         .windows(2)
@@ -113,7 +160,7 @@ where
     counter
 }
 
-fn problem_position<P>(report: &Vec<i32>, mut condition: P) -> Option<usize>
+fn problem_position<P>(report: &[i32], mut condition: P) -> Option<usize>
 where
     P: FnMut(&i32, &i32) -> bool,
 {
@@ -1,14 +1,59 @@
-pub fn solve(input: &str) -> (usize, usize) {
-    (
-        parse_muls(&input, drop_until_part1)
-            .iter()
-            .map(|(a, b)| a * b)
-            .sum::<u32>() as usize,
-        parse_muls(&input, drop_until_part2)
-            .iter()
-            .map(|(a, b)| a * b)
-            .sum::<u32>() as usize,
-    )
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day03;
+
+// there's no such thing as bad input here, everything that doesn't parse is dropped
+impl Solution for Day03 {
+    // the parts differ only in what they parse, so parse for both
+    type Input = [Vec<(u32, u32)>; 2];
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        Ok([
+            parse_muls(input, drop_until_part1),
+            parse_muls(input, drop_until_part2),
+        ])
+    }
+
+    fn part1(&self, [muls, _]: &Self::Input) -> Result<Answer, DayError> {
+        Ok(sum_of_products(muls).into())
+    }
+
+    fn part2(&self, [_, muls]: &Self::Input) -> Result<Answer, DayError> {
+        Ok(sum_of_products(muls).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size instructions, some of them broken, with noise in between
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let mut memory = String::new();
+    for _ in 0..size {
+        let (a, b) = (rng.between(1, 999), rng.between(1, 999));
+        let instruction = match rng.below(8) {
+            0 => "do()".to_owned(),
+            1 => "don't()".to_owned(),
+            2 => format!("mul({},{}", a, b),
+            3 => format!("mul({}*{})", a, b),
+            _ => format!("mul({},{})", a, b),
+        };
+        memory.push_str(&instruction);
+        for _ in 0..rng.below(4) {
+            memory.push(rng.pick(&['?', '(', ')', ',', ' ', '#', 'm', 'u', 'l', 'd', 'o']));
+        }
+    }
+    memory + "\n"
+}
+
+// the factors are u32, so their products fit in a u64, and so does the sum of long inputs
+fn sum_of_products(muls: &[(u32, u32)]) -> u64 {
+    muls.iter().map(|&(a, b)| u64::from(a) * u64::from(b)).sum()
 }
 
 type ParseState<'a> = (&'a str, bool);
@@ -77,3 +122,17 @@ fn parse_int(data: ParseState) -> Result<(ParseState, u32), ParseState> {
         Err(_) => Err(data),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the answers of long generated inputs don't fit in a u32
+    #[test]
+    fn test_long_input() {
+        let input = random_input(40_000, &mut Rng::new(0));
+        let parsed = Day03.parse(&input, &Params::default()).unwrap();
+        let big = |answer| matches!(answer, Answer::Unsigned(n) if n > u32::MAX.into());
+        assert!(big(Day03.part1(&parsed).unwrap()));
+    }
+}
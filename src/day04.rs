@@ -1,33 +1,72 @@
-pub fn solve(input: &str) -> (usize, usize) {
-    let data = parse_input(&input);
+use crate::{
+    generate::{self, Rng},
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    (part1(&data), part2(&data))
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, data: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(data).into())
+    }
+
+    fn part2(&self, data: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(data).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size times size letters of XMAS, at least 3x3 as part2 looks for crosses of 3x3
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    generate::grid(size, size, |_, _| rng.pick(&['X', 'M', 'A', 'S']))
 }
 
 // parse input into vector-of-vectors for easier transpose etc.
 // use u8 instead of char because we don't need utf-8
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    input.lines().map(|l| l.as_bytes().to_owned()).collect()
+fn parse_input(input: &str) -> Result<Vec<Vec<u8>>, DayError> {
+    let data: Vec<Vec<u8>> = input.lines().map(|l| l.as_bytes().to_owned()).collect();
+    // transpose and diagonals need a non-empty rectangle, tile at least 3x3 letters
+    match data.first() {
+        Some(first) if data.iter().any(|l| l.len() != first.len()) => {
+            Err(DayError::WrongFormat("lines differ in length"))
+        }
+        Some(first) if data.len() < 3 || first.len() < 3 => {
+            Err(DayError::WrongFormat("the grid needs at least 3x3 letters"))
+        }
+        Some(_) => Ok(data),
+        None => Err(DayError::WrongFormat("empty input")),
+    }
 }
 
-fn part1(data: &Vec<Vec<u8>>) -> usize {
+fn part1(data: &[Vec<u8>]) -> usize {
     let horizontal: usize = data.iter().map(|l| count_occurences_fw_bw("XMAS", l)).sum();
-    let vertical: usize = transpose(&data)
+    let vertical: usize = transpose(data)
         .iter()
         .map(|l| count_occurences_fw_bw("XMAS", l))
         .sum();
-    let diagonal: usize = diagonals(&data)
+    let diagonal: usize = diagonals(data)
         .iter()
         .map(|l| count_occurences_fw_bw("XMAS", l))
         .sum();
-    let anti_diagonal: usize = diagonals(&transpose(&data))
+    let anti_diagonal: usize = diagonals(&transpose(data))
         .iter()
         .map(|l| count_occurences_fw_bw("XMAS", l))
         .sum();
     horizontal + vertical + diagonal + anti_diagonal
 }
 
-fn count_occurences_fw_bw(word: &str, text: &Vec<u8>) -> usize {
+fn count_occurences_fw_bw(word: &str, text: &[u8]) -> usize {
     let fw = word.as_bytes();
     let bw: Vec<u8> = fw.iter().copied().rev().collect();
     text.windows(word.len())
@@ -35,7 +74,7 @@ fn count_occurences_fw_bw(word: &str, text: &Vec<u8>) -> usize {
         .count()
 }
 
-fn transpose<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
+fn transpose<T>(v: &[Vec<T>]) -> Vec<Vec<T>>
 where
     T: Copy,
 {
@@ -46,7 +85,7 @@ where
         .collect()
 }
 
-fn diagonals<T>(v: &Vec<Vec<T>>) -> Vec<Vec<T>>
+fn diagonals<T>(v: &[Vec<T>]) -> Vec<Vec<T>>
 where
     T: Copy,
 {
@@ -57,11 +96,11 @@ where
     assert!(v.iter().filter(|l| l.len() == n).count() == m);
 
     let mut diags = vec![Vec::<T>::new(); m + n - 1];
-    for d in 0..m {
+    for (d, diag) in diags.iter_mut().enumerate().take(m) {
         let mut i = 0;
         let mut j = d;
         while j < m && i < n {
-            diags[d].push(v[j][i]);
+            diag.push(v[j][i]);
             i += 1;
             j += 1;
         }
@@ -79,7 +118,7 @@ where
     diags
 }
 
-fn tile<T>(grid: &Vec<Vec<T>>) -> Vec<Vec<Vec<T>>>
+fn tile<T>(grid: &[Vec<T>]) -> Vec<Vec<Vec<T>>>
 where
     T: Copy,
 {
@@ -90,14 +129,10 @@ where
     // interesting: with saturating_sub we can 'easily' use usize as slice-bounds
     for i in 0..=rows.saturating_sub(3) {
         for j in 0..=cols.saturating_sub(3) {
-            let mut subtile = Vec::new();
-            for row in i..i + 3 {
-                let mut subtile_row = Vec::new();
-                for col in j..j + 3 {
-                    subtile_row.push(grid[row][col]);
-                }
-                subtile.push(subtile_row);
-            }
+            let subtile = grid[i..i + 3]
+                .iter()
+                .map(|row| row[j..j + 3].to_vec())
+                .collect();
             subtiles.push(subtile);
         }
     }
@@ -105,12 +140,12 @@ where
     subtiles
 }
 
-fn is_xmas(t: &Vec<Vec<u8>>) -> bool {
+fn is_xmas(t: &[Vec<u8>]) -> bool {
     t[0][0] == b'M' && t[0][2] == b'M' && t[1][1] == b'A' && t[2][0] == b'S' && t[2][2] == b'S'
 }
 
 // mirror vertically
-fn mirror(t: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+fn mirror(t: &[Vec<u8>]) -> Vec<Vec<u8>> {
     t.iter()
         .map(|row| row.iter().rev().copied().collect::<Vec<u8>>())
         .collect()
@@ -134,13 +169,13 @@ fn mirror(t: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
 // S S	                              M M
 
 // It would actually be better transform a XMAS (at compile time) and check against those
-fn is_any_xmas(t: &Vec<Vec<u8>>) -> bool {
+fn is_any_xmas(t: &[Vec<u8>]) -> bool {
     is_xmas(t)
         || is_xmas(&transpose(t))
         || is_xmas(&transpose(&mirror(t)))
         || is_xmas(&transpose(&mirror(&transpose(t))))
 }
 
-fn part2(data: &Vec<Vec<u8>>) -> usize {
+fn part2(data: &[Vec<u8>]) -> usize {
     tile(data).iter().filter(|&t| is_any_xmas(t)).count()
 }
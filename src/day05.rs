@@ -2,40 +2,85 @@ use std::collections::{HashMap, HashSet};
 
 use itertools::Itertools;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (rules, pages_list) = parse_input(&input);
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    (
-        part1(&pages_list, &rules) as usize,
-        part2(&pages_list, &rules) as usize,
-    )
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = (Graph, Vec<Pages>);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse_input(input)
+    }
+
+    fn part1(&self, (rules, pages_list): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(pages_list, rules).into())
+    }
+
+    fn part2(&self, (rules, pages_list): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(pages_list, rules)?.into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// rules for every pair of 24 pages in a random order and size updates,
+// about half of them in the right order
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let mut pages: Vec<u32> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    let order = &pages[..24];
+    let mut rules = order
+        .iter()
+        .tuple_combinations()
+        .map(|(before, after)| format!("{}|{}\n", before, after))
+        .collect_vec();
+    rng.shuffle(&mut rules);
+
+    let mut input = rules.concat() + "\n";
+    for _ in 0..size {
+        let mut update = order.to_vec();
+        rng.shuffle(&mut update);
+        // odd, so that there is a middle page
+        update.truncate(2 * rng.between(2, 11) as usize + 1);
+        if rng.chance(0.5) {
+            update.sort_by_key(|page| order.iter().position(|p| p == page));
+        }
+        input += &(update.iter().join(",") + "\n");
+    }
+    input
 }
 
-type Graph = HashMap<u32, HashSet<u32>>;
-type Pages = Vec<u32>;
+pub type Graph = HashMap<u32, HashSet<u32>>;
+pub type Pages = Vec<u32>;
 
-fn parse_input(input: &str) -> (Graph, Vec<Pages>) {
-    let mut rules = HashMap::new();
+fn parse_input(input: &str) -> Result<(Graph, Vec<Pages>), DayError> {
+    let mut rules = Graph::new();
     for l in input.lines().take_while(|l| !l.is_empty()) {
-        let Some((Ok(before), Ok(after))) = l.split("|").map(|n| n.parse::<u32>()).next_tuple()
-        else {
-            panic!("The input does not look like expected");
+        let Some((before, after)) = l.split("|").map(|n| n.parse::<u32>()).next_tuple() else {
+            return Err(DayError::WrongFormat("rules look like 'before|after'"));
         };
 
-        rules.entry(before).or_insert(HashSet::new()).insert(after);
+        rules.entry(before?).or_default().insert(after?);
     }
 
     let pages_list = input
         .lines()
         .skip_while(|l| !l.is_empty())
         .skip(1) // empty delineating line
-        .map(|l| l.split(",").map(|n| n.parse::<u32>().unwrap()).collect())
-        .collect();
+        .map(|l| l.split(",").map(|n| n.parse::<u32>()).collect())
+        .collect::<Result<_, _>>()?;
 
-    (rules, pages_list)
+    Ok((rules, pages_list))
 }
 
-fn part1(pages_list: &Vec<Pages>, rules: &Graph) -> u32 {
+fn part1(pages_list: &[Pages], rules: &Graph) -> u32 {
     pages_list
         .iter()
         .filter(|pages| is_correctly_ordered(pages, rules))
@@ -61,33 +106,30 @@ fn middle_of<T>(values: &[T]) -> &T {
     &values[values.len() / 2]
 }
 
-fn part2(pages_list: &Vec<Pages>, rules: &Graph) -> u32 {
+fn part2(pages_list: &[Pages], rules: &Graph) -> Result<u32, DayError> {
     // note that we cannot just topological_sort(rules) because they contain cycles
     pages_list
         .iter()
         .filter(|pages| !is_correctly_ordered(pages, rules))
         .map(|pages| order_correctly(pages, rules))
-        .map(|pages| *middle_of(&pages))
+        .map(|pages| pages.map(|pages| *middle_of(&pages)))
         .sum()
 }
 
-fn order_correctly(pages: &[u32], rules: &Graph) -> Vec<u32> {
+fn order_correctly(pages: &[u32], rules: &Graph) -> Result<Vec<u32>, DayError> {
     let mut dependencies = Graph::new();
     for (before, &start, after) in split_everywhere(pages) {
         for &end in before.iter().chain(after) {
             // if path_between(start, end, rules) {
             if rules.get(&start).is_some_and(|ends| ends.contains(&end)) {
-                dependencies
-                    .entry(start)
-                    .or_insert(HashSet::new())
-                    .insert(end);
+                dependencies.entry(start).or_default().insert(end);
             }
         }
     }
     topological_sort(&dependencies)
 }
 
-fn topological_sort(graph: &Graph) -> Vec<u32> {
+fn topological_sort(graph: &Graph) -> Result<Vec<u32>, DayError> {
     // See DFS algorithm in
     // https://en.wikipedia.org/wiki/Topological_sorting
 
@@ -100,19 +142,22 @@ fn topological_sort(graph: &Graph) -> Vec<u32> {
         finished: &mut HashSet<u32>,
         doing: &mut HashSet<u32>,
         sorted_reversed: &mut Vec<u32>,
-    ) {
+    ) -> Result<(), DayError> {
         if finished.contains(&node) {
-            return;
+            return Ok(());
         }
         if doing.contains(&node) {
-            panic!("We're in a loop!");
+            return Err(DayError::WrongFormat(
+                "the rules for an update contain a loop",
+            ));
         }
         doing.insert(node);
         for next in graph.get(&node).unwrap_or(&HashSet::new()) {
-            visit(*next, graph, finished, doing, sorted_reversed);
+            visit(*next, graph, finished, doing, sorted_reversed)?;
         }
         finished.insert(node);
         sorted_reversed.push(node);
+        Ok(())
     }
 
     let nodes = graph.keys().cloned().collect::<HashSet<u32>>();
@@ -122,10 +167,10 @@ fn topological_sort(graph: &Graph) -> Vec<u32> {
 
     // note the copied
     while let Some(next) = nodes.difference(&finished).copied().next() {
-        visit(next, graph, &mut finished, &mut doing, &mut sorted);
+        visit(next, graph, &mut finished, &mut doing, &mut sorted)?;
     }
     sorted.reverse();
-    sorted
+    Ok(sorted)
 }
 
 fn inits<T>(v: &[T]) -> Vec<&[T]> {
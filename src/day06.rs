@@ -12,23 +12,70 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use tailcall::tailcall;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (field, guard) = read(&input);
+use crate::{
+    generate::{self, Rng},
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    (part1(&field, guard), part2(&field, guard))
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = (Field, Guard);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        read(input)
+    }
+
+    fn part1(&self, (field, guard): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(field, *guard).into())
+    }
+
+    fn part2(&self, (field, guard): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(field, *guard).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
 }
 
-fn read(input: &str) -> (Field, Guard) {
-    (
+// size times size with obstacles on a tenth of the cells and the guard somewhere
+// part1 walks until the guard leaves, so grids where the guard is stuck in a loop are drawn again
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    loop {
+        let guard = rng.below((size * size) as u64) as usize;
+        let input = generate::grid(size, size, |row, col| {
+            if row * size + col == guard {
+                '^'
+            } else if rng.chance(0.1) {
+                '#'
+            } else {
+                '.'
+            }
+        });
+        let (field, start) = read(&input).expect("Generated grids are valid");
+        let mut seen = HashSet::new();
+        if std::iter::successors(Some(start), |g| field.step(*g))
+            .all(|g| seen.insert((g.pos, g.dir)))
+        {
+            return input;
+        }
+    }
+}
+
+fn read(input: &str) -> Result<(Field, Guard), DayError> {
+    Ok((
         Field {
             obstacles: filter_map_positions(input, |(x, y), c| match c {
-                '#' => Some((x as i32, y as i32)),
+                '#' => Some((x, y)),
                 _ => None,
             })
             .collect(),
             size: filter_map_positions(input, |(x, y), _| Some((x, y)))
                 .last()
-                .unwrap(),
+                .ok_or(DayError::WrongFormat("empty input"))?,
         },
         filter_map_positions(input, |(x, y), c| match c {
             '^' => Some(Guard {
@@ -50,8 +97,8 @@ fn read(input: &str) -> (Field, Guard) {
             _ => None,
         })
         .last()
-        .unwrap(),
-    )
+        .ok_or(DayError::WrongFormat("no guard found"))?,
+    ))
 }
 
 fn filter_map_positions<'a, T, F>(input: &'a str, mut f: F) -> impl Iterator<Item = T> + 'a
@@ -66,11 +113,11 @@ where
                 .enumerate()
                 .map(move |(x, c)| f((x as i32, y as i32), c))
         })
-        .filter_map(|i| i)
+        .flatten()
 }
 
 fn part1(field: &Field, start: Guard) -> usize {
-    std::iter::successors(Some(start), |g| field.step(g.clone()))
+    std::iter::successors(Some(start), |g| field.step(*g))
         .map(|g| g.pos)
         .collect::<HashSet<Position>>()
         .len()
@@ -100,7 +147,7 @@ fn counter_clockwise(dir: Direction) -> Direction {
 type Position = (i32, i32);
 
 #[derive(Copy, Clone, Eq, PartialEq)]
-struct Guard {
+pub struct Guard {
     pos: Position,
     dir: Direction,
 }
@@ -140,18 +187,18 @@ fn bound(guard: Guard, bounds: &(i32, i32)) -> Option<Guard> {
     }
 }
 
-struct Field {
+pub struct Field {
     obstacles: HashSet<Position>,
     size: (i32, i32),
 }
 
 impl Field {
     fn step(&self, guard: Guard) -> Option<Guard> {
-        bound(guard.forward(), &self.size).and_then(|guard| {
+        bound(guard.forward(), &self.size).map(|guard| {
             if self.obstacles.contains(&guard.pos) {
-                Some(guard.back().turn())
+                guard.back().turn()
             } else {
-                Some(guard)
+                guard
             }
         })
     }
@@ -171,7 +218,7 @@ fn part2(field: &Field, start: Guard) -> usize {
         .iter()
         .for_each(|o| obstacles.add_obstacle(*o));
 
-    std::iter::successors(Some(start), |g| field.step(g.clone()))
+    std::iter::successors(Some(start), |g| field.step(*g))
         .collect::<Vec<_>>()
         .into_par_iter()
         .filter_map(|g| {
@@ -291,8 +338,7 @@ impl Intervals {
     fn first_larger_equals(&self, x: i32) -> Option<i32> {
         self.intervals
             .range(..=x)
-            .rev()
-            .next()
+            .next_back()
             .filter(|(_, e)| e >= &&x)
             .or_else(|| self.intervals.range(x..).next())
             .map(|(&s, &_)| s)
@@ -369,7 +415,7 @@ impl Lines {
         // See simpler entry example: https://doc.rust-lang.org/std/collections/btree_map/struct.BTreeMap.html#method.entry
         self.lines
             .entry(dir)
-            .or_insert(BTreeMap::new())
+            .or_default()
             .entry(const_coord)
             .or_insert(Intervals::new())
             .add(start, end);
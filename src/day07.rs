@@ -2,15 +2,81 @@ use std::{iter::zip, num::ParseIntError, str::FromStr};
 
 use itertools::Itertools;
 use rayon::prelude::*;
+use thiserror::Error;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let equations: Vec<Equation> = input.lines().map(|l| l.parse().unwrap()).collect();
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    (part1(&equations) as usize, part2(&equations) as usize)
+// part 2 either checks every equation on one thread, or spreads equations and
+// operator combinations over the rayon pool
+pub struct Day07 {
+    parallel: bool,
+}
+
+impl Day07 {
+    pub const PARALLEL: Day07 = Day07 { parallel: true };
+    pub const SEQUENTIAL: Day07 = Day07 { parallel: false };
+}
+
+impl Solution for Day07 {
+    type Input = Vec<Equation>;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        Ok(input.lines().map(|l| l.parse()).collect::<Result<_, _>>()?)
+    }
+
+    fn part1(&self, equations: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(equations).into())
+    }
+
+    fn part2(&self, equations: &Self::Input) -> Result<Answer, DayError> {
+        Ok(if self.parallel {
+            part2(equations)
+        } else {
+            part2_seq(equations)
+        }
+        .into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// equations of 2 to 12 terms that are built with random operators, some results are
+// off by one so that they can't be made true. Results stay far below u64::MAX
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    (0..size)
+        .map(|_| {
+            let terms = (0..rng.between(2, 12))
+                .map(|_| rng.between(1, 999) as u64)
+                .collect_vec();
+            let mut result = terms[0];
+            for &term in &terms[1..] {
+                let operator = if result < 1_000_000_000_000 {
+                    rng.below(3)
+                } else {
+                    0
+                };
+                result = match operator {
+                    0 => result + term,
+                    1 => result * term,
+                    _ => eval(result, Operator::Concat, term),
+                };
+            }
+            if rng.chance(0.3) {
+                result += 1;
+            }
+            format!("{}: {}\n", result, terms.iter().join(" "))
+        })
+        .collect()
 }
 
 #[derive(Debug)]
-struct Equation {
+pub struct Equation {
     result: u64,
     terms: Vec<u64>,
 }
@@ -38,6 +104,14 @@ fn part2(equations: &[Equation]) -> u64 {
         .sum()
 }
 
+fn part2_seq(equations: &[Equation]) -> u64 {
+    equations
+        .iter()
+        .filter(|e| can_be_true(e, vec![Operator::Add, Operator::Multiply, Operator::Concat]))
+        .map(|e| e.result)
+        .sum()
+}
+
 fn eval(a: u64, op: Operator, b: u64) -> u64 {
     match op {
         Operator::Add => a + b,
@@ -72,7 +146,7 @@ fn is_true(equation: &Equation, operators: &[Operator]) -> bool {
 }
 
 fn can_be_true(equation: &Equation, options: Vec<Operator>) -> bool {
-    operator_combinations_for(&equation, options).any(|ops| is_true(&equation, &ops))
+    operator_combinations_for(equation, options).any(|ops| is_true(equation, &ops))
 }
 
 fn can_be_true_par(equation: &Equation, options: Vec<Operator>) -> bool {
@@ -83,7 +157,7 @@ fn can_be_true_par(equation: &Equation, options: Vec<Operator>) -> bool {
     (0..options.len().pow(n_ops))
         .into_par_iter()
         .filter_map(|n| combinations_for(n, n_ops, &options))
-        .any(|ops| is_true(&equation, &ops))
+        .any(|ops| is_true(equation, &ops))
 }
 
 fn operator_combinations_for(
@@ -136,8 +210,9 @@ where
     Some(v)
 }
 
-#[derive(Debug)]
-struct ParseEquationError;
+#[derive(Error, Debug)]
+#[error("Wrong Format")]
+pub struct ParseEquationError;
 
 // so we can use '?' on Result<_, ParseIntError> in a function returning Result<_, ParseEquationError>
 impl From<ParseIntError> for ParseEquationError {
@@ -5,18 +5,52 @@ use std::{
 
 use itertools::iproduct;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (antennas, corner) = read(&input);
+use crate::{
+    generate::{self, Rng},
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day08;
+
+// every character is either empty or an antenna, so reading cannot fail
+impl Solution for Day08 {
+    type Input = (ResonancePoints, Position);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        Ok(read(input))
+    }
+
+    fn part1(&self, (antennas, corner): &Self::Input) -> Result<Answer, DayError> {
+        Ok(count_antinodes(antennas, ((0, 0), *corner), antinodes_part1).into())
+    }
 
-    (
-        count_antinodes(&antennas, ((0, 0), corner), antinodes_part1),
-        count_antinodes(&antennas, ((0, 0), corner), antinodes_part2),
-    )
+    fn part2(&self, (antennas, corner): &Self::Input) -> Result<Answer, DayError> {
+        Ok(count_antinodes(antennas, ((0, 0), *corner), antinodes_part2).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size times size with antennas on a few cells, more frequencies for larger maps
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    let frequencies: Vec<_> = ('0'..='9').chain('a'..='z').chain('A'..='Z').collect();
+    let frequencies = &frequencies[..(size / 4).clamp(1, frequencies.len())];
+    generate::grid(size, size, |_, _| {
+        if rng.chance(0.03) {
+            rng.pick(frequencies)
+        } else {
+            '.'
+        }
+    })
 }
 
 // Positive y-direction is up!
-type Position = (i32, i32);
-type ResonancePoints = HashMap<char, Vec<Position>>;
+pub type Position = (i32, i32);
+pub type ResonancePoints = HashMap<char, Vec<Position>>;
 
 fn read(input: &str) -> (ResonancePoints, (i32, i32)) {
     let (mut x_max, mut y_max) = (0, 0);
@@ -42,8 +76,8 @@ fn count_antinodes(
     generate_antinodes: fn(&[Position], (Position, Position)) -> Vec<Position>,
 ) -> usize {
     antennas
-        .iter()
-        .flat_map(|(_, positions)| generate_antinodes(&positions, bounds))
+        .values()
+        .flat_map(|positions| generate_antinodes(positions, bounds))
         .collect::<HashSet<_>>()
         .len()
 }
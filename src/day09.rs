@@ -1,18 +1,62 @@
-pub fn solve(input: &str) -> (usize, usize) {
-    let numbers = input
-        .trim()
-        .chars()
-        .map(|c| c.to_digit(10).unwrap())
-        .collect::<Vec<_>>();
+use crate::{
+    generate::Rng,
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse(input)
+    }
+
+    fn part1(&self, numbers: &Self::Input) -> Result<Answer, DayError> {
+        let mut disk = make_explicit_disk_map(numbers);
+        compact_p1(&mut disk);
+        Ok(checksum(&disk).into())
+    }
+
+    fn part2(&self, numbers: &Self::Input) -> Result<Answer, DayError> {
+        let mut disk = make_explicit_disk_map(numbers);
+        compact_p2(&mut disk);
+        Ok(checksum(&disk).into())
+    }
 
-    let mut disk = make_explicit_disk_map(&numbers);
-    compact_p1(&mut disk);
-    let p1 = checksum(&disk);
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
 
-    let mut disk = make_explicit_disk_map(&numbers);
-    compact_p2(&mut disk);
-    let p2 = checksum(&disk);
-    (p1, p2)
+// size digits, files are never empty
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let digits = (0..size.max(1)).map(|i| {
+        let length = if i % 2 == 0 {
+            rng.between(1, 9)
+        } else {
+            rng.between(0, 9)
+        };
+        char::from_digit(length as u32, 10).expect("Lengths are single digits")
+    });
+    digits.chain(['\n']).collect()
+}
+
+fn parse(input: &str) -> Result<Vec<u32>, DayError> {
+    let numbers: Vec<u32> = input
+        .trim()
+        .chars()
+        .map(|c| c.to_digit(10))
+        .collect::<Option<_>>()
+        .ok_or(DayError::WrongFormat(
+            "the disk map consists of digits only",
+        ))?;
+    // compact_p1 needs at least one block
+    if numbers.iter().step_by(2).all(|&n| n == 0) {
+        return Err(DayError::WrongFormat("the disk contains no files"));
+    }
+    Ok(numbers)
 }
 
 type ExplicitDiskMap = Vec<Option<usize>>;
@@ -1,9 +1,40 @@
 use std::collections::HashSet;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let map = Map::new(&input);
+use crate::{
+    generate::{self, Rng},
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
 
-    (part1(&map), part2(&map))
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Map;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        Map::new(input)
+    }
+
+    fn part1(&self, map: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(map).into())
+    }
+
+    fn part2(&self, map: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(map).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size times size, mostly rising to the bottom right so that there are trails
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    generate::grid(size, size, |row, col| {
+        let height = (row + col) as u64 + rng.below(2);
+        char::from_digit((height % 10) as u32, 10).expect("Heights are single digits")
+    })
 }
 
 fn part1(map: &Map) -> usize {
@@ -25,27 +56,31 @@ fn part2(map: &Map) -> usize {
 type Position = (usize, usize);
 
 #[derive(Debug)]
-struct Map {
+pub struct Map {
     data: Vec<Vec<u32>>,
     max: Position,
 }
 
 impl Map {
-    fn new(s: &str) -> Map {
+    fn new(s: &str) -> Result<Map, DayError> {
         let data: Vec<Vec<_>> = s
             .lines()
-            .map(|l| l.chars().map(|c| c.to_digit(10).unwrap()).collect())
-            .collect();
+            .map(|l| l.chars().map(|c| c.to_digit(10)).collect())
+            .collect::<Option<_>>()
+            .ok_or(DayError::WrongFormat("the map consists of digits only"))?;
 
         // 0-size Maps are disallowed
-        let max = (data.len(), data[0].len());
-
-        data.iter().for_each(|l| {
-            if l.len() != max.1 {
-                panic!();
-            }
-        });
-        Map { data, max }
+        let max = (
+            data.len(),
+            data.first()
+                .ok_or(DayError::WrongFormat("empty map"))?
+                .len(),
+        );
+
+        if data.iter().any(|l| l.len() != max.1) {
+            return Err(DayError::WrongFormat("the map is not rectangular"));
+        }
+        Ok(Map { data, max })
     }
 
     fn at(&self, p: Position) -> u32 {
@@ -77,15 +112,15 @@ impl Map {
             .collect()
     }
 
-    fn iter(&self) -> MapIter {
+    fn iter(&self) -> MapIter<'_> {
         self.into_iter()
     }
 
-    fn reachable_from(&self, p: Position) -> PathIter {
+    fn reachable_from(&self, p: Position) -> PathIter<'_> {
         PathIter::new(self, p)
     }
 
-    fn all_paths_from(&self, p: Position) -> AllPathIter {
+    fn all_paths_from(&self, p: Position) -> AllPathIter<'_> {
         AllPathIter::new(self, p)
     }
 }
@@ -99,7 +134,7 @@ impl<'a> IntoIterator for &'a Map {
     }
 }
 
-struct MapIter<'a> {
+pub struct MapIter<'a> {
     map: &'a Map,
     current: Position,
 }
@@ -179,11 +214,9 @@ impl<'a> Iterator for AllPathIter<'a> {
     type Item = Position;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(here) = self.todo.pop() {
+        self.todo.pop().inspect(|&here| {
             self.todo
                 .extend_from_slice(&self.map.reachable_neighbors(here));
-            return Some(here);
-        }
-        None
+        })
     }
 }
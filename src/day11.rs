@@ -1,49 +1,155 @@
-use std::{collections::HashMap, hash::Hash, num::ParseIntError, ops::AddAssign, str::FromStr};
+use std::{collections::HashMap, hash::Hash, num::ParseIntError, str::FromStr};
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    generate::Rng,
+    params::{Param, ParamKind, Params},
+    solution::{Answer, DayError, Solution},
+};
+
+static PARAMS: &[Param] = &[
+    Param {
+        name: "blinks1",
+        kind: ParamKind::Unsigned { min: 0, max: 1000 },
+        default: "25",
+        help: "Blinks in part 1",
+    },
+    BLINKS2,
+];
+
+// part 1 of the digits keeps every stone, and their number grows by half with every blink
+static DIGITS_PARAMS: &[Param] = &[
+    Param {
+        name: "blinks1",
+        kind: ParamKind::Unsigned { min: 0, max: 30 },
+        default: "25",
+        help: "Blinks in part 1",
+    },
+    BLINKS2,
+];
+
+const BLINKS2: Param = Param {
+    name: "blinks2",
+    kind: ParamKind::Unsigned { min: 0, max: 1000 },
+    default: "75",
+    help: "Blinks in part 2",
+};
+
+pub struct Blinks {
+    part1: usize,
+    part2: usize,
+}
+
+impl Blinks {
+    fn new(params: &Params) -> Blinks {
+        Blinks {
+            part1: params.unsigned("blinks1") as usize,
+            part2: params.unsigned("blinks2") as usize,
+        }
+    }
+}
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let stones: Vec<_> = input
-        .split_whitespace()
-        .map(|l| l.parse().unwrap())
-        .collect();
+// stones as plain numbers
+pub struct Day11;
 
-    let _p1 = part1(stones.iter().cloned().collect());
-    let _p2 = part2(&stones);
+// stones as digit vectors, part 1 keeps every single stone
+pub struct Day11Digits;
 
-    let stones: Vec<_> = input
-        .split_whitespace()
-        .map(|l| l.parse().unwrap()) // this deduces Stone2 because of the signature of part12
-        .collect();
+impl Solution for Day11 {
+    type Input = (Vec<Stone2>, Blinks);
 
-    (part12(&stones), part22(&stones))
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Input, DayError> {
+        let stones = input
+            .split_whitespace()
+            .map(|l| l.parse())
+            .collect::<Result<_, _>>()?;
+        Ok((stones, Blinks::new(params)))
+    }
+
+    fn part1(&self, (stones, blinks): &Self::Input) -> Result<Answer, DayError> {
+        Ok(do_rounds(stones, blinks.part1)
+            .ok_or(DayError::Overflow)?
+            .into())
+    }
+
+    fn part2(&self, (stones, blinks): &Self::Input) -> Result<Answer, DayError> {
+        Ok(do_rounds(stones, blinks.part2)
+            .ok_or(DayError::Overflow)?
+            .into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size stones below a million
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1)).map(|_| rng.below(1_000_000)).join(" ") + "\n"
+}
+
+impl Solution for Day11Digits {
+    type Input = (Vec<Stone>, Blinks);
+
+    fn params(&self) -> &'static [Param] {
+        DIGITS_PARAMS
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Input, DayError> {
+        let stones = input
+            .split_whitespace()
+            .map(|l| l.parse())
+            .collect::<Result<_, _>>()?;
+        Ok((stones, Blinks::new(params)))
+    }
+
+    fn part1(&self, (stones, blinks): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(stones.clone(), blinks.part1).into())
+    }
+
+    fn part2(&self, (stones, blinks): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(stones, blinks.part2)
+            .ok_or(DayError::Overflow)?
+            .into())
+    }
 }
 
 // Most significant digit is last,
 // leading zeros are trimmed  <- interesting: how do we enforce that?
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Stone(Vec<u8>);
+pub struct Stone(Vec<u8>);
 
-fn part1(stones: Vec<Stone>) -> usize {
+fn part1(stones: Vec<Stone>, blinks: usize) -> usize {
     std::iter::successors(Some(stones), |stones| {
         Some(stones.iter().flat_map(|s| blink_at(s.clone())).collect())
     })
-    .take(26)
+    .take(blinks + 1)
     .last()
     .unwrap()
     .len()
 }
 
-fn part2(stones: &[Stone]) -> usize {
+// None if there are too many stones to count in a usize
+fn part2(stones: &[Stone], blinks: usize) -> Option<usize> {
     // order does not matter, so we can compress the stones into a hashmap, and do every operation just once
-    let mut stone_map: HashMap<Stone, usize> = accumulate(stones.iter().map(|s| (s.clone(), 1)));
+    let mut stone_map: HashMap<Stone, usize> = accumulate(stones.iter().map(|s| (s.clone(), 1)))?;
 
-    for _ in 0..75 {
+    for _ in 0..blinks {
         stone_map = accumulate(
             stone_map
                 .into_iter()
                 .flat_map(|(stone, count)| blink_at(stone).into_iter().map(move |s| (s, count))),
-        );
+        )?;
     }
-    stone_map.iter().map(|(_, count)| count).sum()
+    stone_map
+        .values()
+        .try_fold(0usize, |sum, &n| sum.checked_add(n))
 }
 
 fn blink_at(stone: Stone) -> Vec<Stone> {
@@ -91,8 +197,9 @@ impl Stone {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-struct ParseStoneError;
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("Not a number")]
+pub struct ParseStoneError;
 
 impl FromStr for Stone {
     type Err = ParseStoneError;
@@ -120,22 +227,23 @@ impl FromStr for Stone {
     }
 }
 
-fn accumulate<T, N, I>(values: I) -> HashMap<T, N>
+// the counts per key, None if one of them overflows
+fn accumulate<T, I>(values: I) -> Option<HashMap<T, usize>>
 where
     T: Eq + Hash,
-    N: Default + AddAssign,
-    I: IntoIterator<Item = (T, N)>,
+    I: IntoIterator<Item = (T, usize)>,
 {
     values
         .into_iter()
-        .fold(HashMap::new(), |mut acc, (key, value)| {
-            *acc.entry(key).or_default() += value;
-            acc
+        .try_fold(HashMap::new(), |mut acc, (key, value)| {
+            let count = acc.entry(key).or_default();
+            *count = value.checked_add(*count)?;
+            Some(acc)
         })
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
-struct Stone2 {
+pub struct Stone2 {
     number: usize,
     digits: u32,
 }
@@ -195,32 +303,27 @@ fn blink_at2(stone: Stone2) -> Vec<Stone2> {
     }
 }
 
-fn do_rounds(stones: &[Stone2], rounds: usize) -> usize {
-    let mut stone_map = accumulate(stones.iter().map(|s| (s.clone(), 1)));
+// None if there are too many stones to count in a usize
+fn do_rounds(stones: &[Stone2], rounds: usize) -> Option<usize> {
+    let mut stone_map = accumulate(stones.iter().map(|s| (s.clone(), 1)))?;
 
     for _ in 0..rounds {
         stone_map = accumulate(
             stone_map
                 .into_iter()
                 .flat_map(|(stone, count)| blink_at2(stone).into_iter().map(move |s| (s, count))),
-        );
+        )?;
     }
-    stone_map.iter().map(|(_, count)| count).sum()
-}
-
-fn part12(stones: &[Stone2]) -> usize {
-    do_rounds(stones, 25)
-}
-
-fn part22(stones: &[Stone2]) -> usize {
-    do_rounds(stones, 75)
+    stone_map
+        .values()
+        .try_fold(0usize, |sum, &n| sum.checked_add(n))
 }
 
 impl FromStr for Stone2 {
     type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.parse().map(|ds| Stone2::new(ds))
+        s.parse().map(Stone2::new)
     }
 }
 
@@ -295,4 +398,24 @@ mod tests {
         let result = stone.split();
         assert_eq!(result, [Stone2::new(10), Stone2::new(1)]);
     }
+
+    #[test]
+    fn test_blinks() {
+        let blinks = |declared, n: &str| {
+            Params::resolve(declared, &[("blinks1".to_owned(), n.to_owned())]).is_ok()
+        };
+        assert!(blinks(Day11.params(), "100"));
+        assert!(blinks(Day11Digits.params(), "30"));
+        assert!(!blinks(Day11Digits.params(), "100"));
+    }
+
+    #[test]
+    fn test_too_many_stones() {
+        let stones = [Stone2::new(125), Stone2::new(17)];
+        assert_eq!(do_rounds(&stones, 25), Some(55312));
+        assert_eq!(do_rounds(&stones, 200), None);
+        let stones = ["125".parse().unwrap(), "17".parse().unwrap()];
+        assert_eq!(part2(&stones, 25), Some(55312));
+        assert_eq!(part2(&stones, 200), None);
+    }
 }
@@ -4,18 +4,55 @@ use std::{
     ops::Index,
 };
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let garden = Garden::new(input);
+use crate::{
+    generate::{self, Rng},
+    params::Params,
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Garden;
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        Garden::new(input)
+    }
+
+    fn part1(&self, garden: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(garden).into())
+    }
+
+    fn part2(&self, garden: &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(garden).into())
+    }
 
-    (part1(&garden), part2(&garden))
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size rows that are half again as wide, plots mostly continue a neighbouring region
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let (rows, cols) = (size.max(3), size.max(3) * 3 / 2);
+    let mut plots = Vec::with_capacity(rows * cols);
+    for i in 0..rows * cols {
+        let plot = match rng.below(10) {
+            0..=3 if i % cols > 0 => plots[i - 1],
+            4..=7 if i >= cols => plots[i - cols],
+            _ => (b'A' + rng.below(26) as u8) as char,
+        };
+        plots.push(plot);
+    }
+    generate::grid(rows, cols, |row, col| plots[row * cols + col])
 }
 
 fn part1(garden: &Garden) -> usize {
-    garden.iter_regions().map(|r| cost1(&r, &garden)).sum()
+    garden.iter_regions().map(|r| cost1(&r, garden)).sum()
 }
 
 fn part2(garden: &Garden) -> usize {
-    garden.iter_regions().map(|r| cost2(&r, &garden)).sum()
+    garden.iter_regions().map(|r| cost2(&r, garden)).sum()
 }
 
 fn cost1(region: &HashSet<MonadicIndex>, garden: &Garden) -> usize {
@@ -59,7 +96,7 @@ fn fences(index: MonadicIndex, garden: &Garden) -> impl Iterator<Item = Fence> +
     .filter_map(move |side| {
         let needs_fence = garden
             .neighbor(here, side)
-            .map_or(true, |neighbor| garden[here] != garden[neighbor]);
+            .is_none_or(|neighbor| garden[here] != garden[neighbor]);
         needs_fence.then_some(Fence {
             location: here,
             side,
@@ -68,7 +105,7 @@ fn fences(index: MonadicIndex, garden: &Garden) -> impl Iterator<Item = Fence> +
 }
 
 #[derive(Debug)]
-struct Garden {
+pub struct Garden {
     rows: usize,
     cols: usize,
     plots: Vec<u8>,
@@ -89,7 +126,6 @@ struct Fence {
     side: Direction,
 }
 
-
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 enum Direction {
     Left,
@@ -123,16 +159,19 @@ impl Fence {
 }
 
 impl Garden {
-    fn new(input: &str) -> Self {
+    fn new(input: &str) -> Result<Self, DayError> {
         let (rows, cols) = (
-            input.chars().take_while(|&c| c != '\n').count(),
             input.lines().count(),
+            input.chars().take_while(|&c| c != '\n').count(),
         );
+        if cols == 0 || input.lines().any(|l| l.len() != cols) {
+            return Err(DayError::WrongFormat("the garden is not rectangular"));
+        }
         let plots = input
             .lines()
             .flat_map(|l| l.as_bytes().to_owned())
             .collect();
-        Garden { rows, cols, plots }
+        Ok(Garden { rows, cols, plots })
     }
 
     fn iter_regions(&self) -> impl Iterator<Item = HashSet<MonadicIndex>> {
@@ -157,7 +196,7 @@ impl Garden {
         }
 
         uf.into_sets()
-            .map(|s| s.into_iter().map(|i| MonadicIndex(i)).collect())
+            .map(|s| s.into_iter().map(MonadicIndex).collect())
     }
 
     fn to_monadic(&self, index: DiadicIndex) -> MonadicIndex {
@@ -272,7 +311,7 @@ impl UnionFind {
 
         for i in 0..self.nodes.len() {
             let root = self.find(i);
-            groups.entry(root).or_insert_with(HashSet::new).insert(i);
+            groups.entry(root).or_default().insert(i);
         }
 
         groups.into_values()
@@ -1,23 +1,77 @@
+use itertools::Itertools;
 use std::{iter::from_fn, num::ParseIntError, str::FromStr};
 use thiserror::Error;
 
+use crate::{
+    generate::Rng,
+    params::{Param, ParamKind, Params},
+    solution::{Answer, DayError, Solution as PuzzleSolution},
+};
+
 type StoreInt = u64;
 type CalcInt = i128;
 
-const PART2_SHIFT: StoreInt = 10000000000000;
+// the shift can be set with --param, so the bounds are checked against the largest one allowed
+const MAX_SHIFT: StoreInt = 1000000000000000;
+
+const_assert!(u64::MAX > 2 * MAX_SHIFT);
+const_assert!(i128::MAX > 4 * MAX_SHIFT as CalcInt * MAX_SHIFT as CalcInt);
+
+pub struct Day13;
+
+impl PuzzleSolution for Day13 {
+    type Input = (Vec<Machine>, StoreInt);
+
+    fn params(&self) -> &'static [Param] {
+        &[Param {
+            name: "shift",
+            kind: ParamKind::Unsigned {
+                min: 0,
+                max: MAX_SHIFT,
+            },
+            default: "10000000000000",
+            help: "Added to the prize coordinates in part 2",
+        }]
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Input, DayError> {
+        let machines = Machine::parse_all(input).collect::<Result<_, _>>()?;
+        Ok((machines, params.unsigned("shift")))
+    }
 
-const_assert!(u64::MAX > 2 * PART2_SHIFT);
-const_assert!(i128::MAX > 4 * PART2_SHIFT as CalcInt * PART2_SHIFT as CalcInt);
+    fn part1(&self, (machines, _): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(machines).into())
+    }
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let machines = Machine::parse_all(input)
-        .collect::<Result<Vec<_>, _>>()
-        .unwrap();
+    fn part2(&self, (machines, shift): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(machines, *shift).into())
+    }
 
-    (part1(&machines), part2(&machines))
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
 }
 
-fn part1(machines: &[Machine]) -> usize {
+// size machines, most prizes can be won with at most 100 presses of each button
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            let a = [rng.between(10, 99), rng.between(10, 99)];
+            let b = [rng.between(10, 99), rng.between(10, 99)];
+            let (a_presses, b_presses) = (rng.between(0, 100), rng.between(0, 100));
+            let mut prize = [0, 1].map(|i| a_presses * a[i] + b_presses * b[i]);
+            if rng.chance(0.3) {
+                prize[0] += rng.between(1, 50);
+            }
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a[0], a[1], b[0], b[1], prize[0], prize[1]
+            )
+        })
+        .join("\n")
+}
+
+fn part1(machines: &[Machine]) -> StoreInt {
     machines
         .iter()
         .filter_map(|m| m.solve())
@@ -25,11 +79,11 @@ fn part1(machines: &[Machine]) -> usize {
         .sum()
 }
 
-fn part2(machines: &[Machine]) -> usize {
+fn part2(machines: &[Machine], shift: StoreInt) -> StoreInt {
     machines
         .iter()
         .map(|m| Machine {
-            prize: m.prize.map(|x| x + PART2_SHIFT),
+            prize: m.prize.map(|x| x + shift),
             a_action: m.a_action,
             b_action: m.b_action,
         })
@@ -39,7 +93,7 @@ fn part2(machines: &[Machine]) -> usize {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct Machine {
+pub struct Machine {
     prize: [StoreInt; 2],
     a_action: [StoreInt; 2],
     b_action: [StoreInt; 2],
@@ -47,6 +101,10 @@ struct Machine {
 
 impl Machine {
     fn solve(&self) -> Option<Solution> {
+        fn divmod(x: CalcInt, y: CalcInt) -> (CalcInt, CalcInt) {
+            (x / y, x % y)
+        }
+
         // prize = a_presses * a_action + b_presses * b_action
         // is a 2x2 LSE, use Cramer's rule to solve
         let [p1, p2] = self.prize.map(|x| x as CalcInt);
@@ -66,18 +124,14 @@ impl Machine {
             return None;
         }
 
-        return StoreInt::try_from(a_presses)
+        StoreInt::try_from(a_presses)
             .ok()
-            .zip_with(StoreInt::try_from(b_presses).ok(), Solution::new);
-
-        fn divmod(x: CalcInt, y: CalcInt) -> (CalcInt, CalcInt) {
-            (x / y, x % y)
-        }
+            .zip_with(StoreInt::try_from(b_presses).ok(), Solution::new)
     }
 }
 
 #[derive(Error, Debug)]
-enum MachineParseError {
+pub enum MachineParseError {
     #[error("Wrong Format")]
     WrongNumberFormat(#[from] ParseIntError),
     #[error("Wrong Format")]
@@ -127,14 +181,14 @@ trait ParseIncrementally: FromStr {
     fn incremental_parse(s: &str) -> (Result<Self, Self::Err>, &str) {
         fn split_n_newlines(n: usize, s: &str) -> (&str, &str) {
             if n == 0 {
-                return ("", &s);
+                return ("", s);
             }
             let newlines: Vec<_> = s.match_indices('\n').take(n).collect();
             if newlines.len() == n {
                 let split_pos = newlines[n - 1].0;
                 (&s[..split_pos], &s[split_pos + 1..])
             } else {
-                (&s, "")
+                (s, "")
             }
         }
 
@@ -173,7 +227,7 @@ impl Solution {
         }
     }
 
-    fn cost(&self) -> usize {
-        self.a_presses as usize * 3 + self.b_presses as usize * 1
+    fn cost(&self) -> StoreInt {
+        self.a_presses * 3 + self.b_presses
     }
 }
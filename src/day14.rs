@@ -3,36 +3,116 @@ use std::{collections::HashMap, hash::Hash, num::ParseIntError, str::FromStr};
 use itertools::Itertools;
 use thiserror::Error;
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let robots: Vec<_> = input.lines().map(|l| l.parse().unwrap()).collect();
-    (part1(&robots), part2(robots).unwrap())
+use crate::{
+    generate::Rng,
+    params::{Param, ParamKind, Params},
+    solution::{Answer, DayError, Solution},
+};
+
+pub struct Day14;
+
+// the example is played in a smaller room
+static PARAMS: &[Param] = &[
+    Param {
+        name: "width",
+        kind: COORD,
+        default: "101",
+        help: "Width of the room",
+    },
+    Param {
+        name: "height",
+        kind: COORD,
+        default: "103",
+        help: "Height of the room",
+    },
+    Param {
+        name: "seconds",
+        // no time at all is fine, an empty room is not
+        kind: ParamKind::Unsigned {
+            min: 0,
+            max: Coord::MAX as u64,
+        },
+        default: "100",
+        help: "Seconds the robots move in part 1",
+    },
+];
+
+const COORD: ParamKind = ParamKind::Unsigned {
+    min: 1,
+    max: Coord::MAX as u64,
+};
+
+impl Solution for Day14 {
+    type Input = (Vec<Robot>, Pos, Coord);
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Input, DayError> {
+        let robots = input.lines().map(|l| l.parse()).collect::<Result<_, _>>()?;
+        // fits, the kind is bounded by Coord::MAX
+        let coord = |name| params.unsigned(name) as Coord;
+        Ok((robots, [coord("width"), coord("height")], coord("seconds")))
+    }
+
+    fn part1(&self, (robots, extent, seconds): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(robots, *extent, *seconds).into())
+    }
+
+    fn part2(&self, (robots, extent, _): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(robots.clone(), *extent)
+            .ok_or(DayError::NoSolution)?
+            .into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// size robots in the room of the default width and height
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    (0..size.max(1))
+        .map(|_| {
+            format!(
+                "p={},{} v={},{}\n",
+                rng.between(0, 100),
+                rng.between(0, 102),
+                rng.between(-99, 99),
+                rng.between(-99, 99)
+            )
+        })
+        .collect()
 }
 
 type Coord = i16;
 type Pos = [Coord; 2];
 type Vel = [Coord; 2];
 
-const EXTENT: Pos = [101, 103];
-
-fn part1(robots: &[Robot]) -> usize {
+fn part1(robots: &[Robot], extent: Pos, seconds: Coord) -> usize {
     robots
-        .into_iter()
-        .map(|r| r.evolved_by(100).pos)
-        .filter_map(|p| quadrant(p))
+        .iter()
+        .map(|r| r.evolved_by(seconds, extent).pos)
+        .filter_map(|p| quadrant(p, extent))
         .counted()
         .into_values()
         .product()
 }
 
-fn part2(mut robots: Vec<Robot>) -> Option<usize> {
+fn part2(mut robots: Vec<Robot>, extent: Pos) -> Option<usize> {
     let (mut min_non_symmetric, mut t_non_symmetric) = (robots.len(), 0);
-    for i in 0..101 * 103 {
+    // the positions repeat after width * height seconds
+    for i in 0..extent[0] as usize * extent[1] as usize {
         let non_symmetric = count_non_symmetric(&robots);
         if non_symmetric < min_non_symmetric {
             min_non_symmetric = non_symmetric;
             t_non_symmetric = i;
         }
-        robots = robots.into_iter().map(|r| r.evolved_by(1)).collect();
+        robots = robots
+            .into_iter()
+            .map(|r| r.evolved_by(1, extent))
+            .collect();
     }
     Some(t_non_symmetric)
 }
@@ -73,8 +153,8 @@ fn count_non_symmetric(robots: &[Robot]) -> usize {
     non_symmetric
 }
 
-#[derive(Debug)]
-struct Robot {
+#[derive(Debug, Clone)]
+pub struct Robot {
     pos: Pos,
     vel: Vel,
 }
@@ -92,31 +172,23 @@ impl Robot {
         Robot { pos, vel }
     }
 
-    fn new_constrained(pos: Pos, vel: Vel, max: Pos) -> Robot {
-        fn constrained(pos: Pos, max: Pos) -> Pos {
-            // place c into the [0, m) interval
-            fn constrain(c: Coord, m: Coord) -> Coord {
-                ((c % m) + m) % m
-            }
-            [constrain(pos[0], max[0]), constrain(pos[1], max[1])]
-        }
-        Self::new(constrained(pos, max), vel)
-    }
-
-    fn evolved_by(&self, t: Coord) -> Robot {
-        fn evolve(p: Coord, v: Coord, t: Coord) -> Coord {
-            t.checked_mul(v).and_then(|d| d.checked_add(p)).unwrap()
+    fn evolved_by(&self, t: Coord, extent: Pos) -> Robot {
+        // t * v of two i16 always fits in an i32, and so does adding p
+        fn evolve(p: Coord, v: Coord, t: Coord, m: Coord) -> Coord {
+            (p as i32 + t as i32 * v as i32).rem_euclid(m as i32) as Coord
         }
         let [px, py] = self.pos;
         let [vx, vy] = self.vel;
         // We don't have to make sure we're in the field after every step
-        // (as long as we don't get overflow problems)
-        Self::new_constrained([evolve(px, vx, t), evolve(py, vy, t)], self.vel, EXTENT)
+        Self::new(
+            [evolve(px, vx, t, extent[0]), evolve(py, vy, t, extent[1])],
+            self.vel,
+        )
     }
 }
 
-fn quadrant(p: Pos) -> Option<Quadrant> {
-    match (p[0] - EXTENT[0] / 2, p[1] - EXTENT[1] / 2) {
+fn quadrant(p: Pos, extent: Pos) -> Option<Quadrant> {
+    match (p[0] - extent[0] / 2, p[1] - extent[1] / 2) {
         (x, y) if x > 0 && y < 0 => Some(Quadrant::NorthEast),
         (x, y) if x < 0 && y < 0 => Some(Quadrant::NorthWest),
         (x, y) if x < 0 && y > 0 => Some(Quadrant::SouthWest),
@@ -196,7 +268,7 @@ trait AccIntoVecExt: Iterator {
 impl<I: Iterator> AccIntoVecExt for I {}
 
 #[derive(Error, Debug)]
-enum RobotParseError {
+pub enum RobotParseError {
     #[error("Wrong Format")]
     WrongNumberFormat(#[from] ParseIntError),
     #[error("Wrong Format")]
@@ -235,3 +307,18 @@ fn show(robots: &[Robot]) {
         println!(); // New line after each row
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evolved_by() {
+        let robot = Robot::new([2, 4], [2, -3]);
+        assert_eq!(robot.evolved_by(5, [11, 7]).pos, [1, 3]);
+        // t * v is far beyond an i16
+        let robot = Robot::new([0, 0], [99, -99]);
+        assert_eq!(robot.evolved_by(1000, [101, 103]).pos, [20, 86]);
+        assert_eq!(robot.evolved_by(0, [101, 103]).pos, [0, 0]);
+    }
+}
@@ -1,25 +1,73 @@
 use core::fmt;
 
+use itertools::Itertools;
+
 use crate::{
+    generate::{self, Rng},
     graph::bfs,
     grid::{Direction, Grid, GridParseError, GridParser, Index, Point, Vector},
+    params::Params,
+    solution::{Answer, DayError, Solution},
 };
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (warehouse, movements) = parse(input);
-    let warehouse2 = warehouse.make_part2();
-    (part1(warehouse, &movements), part2(warehouse2, &movements))
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Warehouse, Vec<Direction>);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse(input)
+    }
+
+    fn part1(&self, (warehouse, movements): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part1(warehouse.clone(), movements).into())
+    }
+
+    fn part2(&self, (warehouse, movements): &Self::Input) -> Result<Answer, DayError> {
+        Ok(part2(warehouse.make_part2(), movements).into())
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// a size times size warehouse with a wall around it and 20 times size moves
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let size = size.max(3);
+    let inner = (size - 2) as u64;
+    let robot = (1 + rng.below(inner) as usize, 1 + rng.below(inner) as usize);
+    let mut input = generate::grid(size, size, |row, col| {
+        if row == 0 || col == 0 || row == size - 1 || col == size - 1 {
+            '#'
+        } else if (row, col) == robot {
+            '@'
+        } else {
+            match rng.below(20) {
+                0 => '#',
+                1..=4 => 'O',
+                _ => '.',
+            }
+        }
+    });
+    input.push('\n');
+    let moves = (0..20 * size).map(|_| rng.pick(&['^', '>', 'v', '<']));
+    for line in &moves.chunks(70) {
+        input.extend(line);
+        input.push('\n');
+    }
+    input
 }
 
 fn part1(mut warehouse: Warehouse, movements: &[Direction]) -> usize {
-    for &m in movements.into_iter() {
+    for &m in movements.iter() {
         warehouse.do_move(m)
     }
     warehouse.score()
 }
 
 fn part2(mut warehouse: Warehouse2, movements: &[Direction]) -> usize {
-    for &m in movements.into_iter() {
+    for &m in movements.iter() {
         warehouse.do_move(m)
     }
     warehouse.score()
@@ -38,13 +86,13 @@ enum Thing {
 }
 
 #[derive(Debug, Clone)]
-struct Warehouse {
+pub struct Warehouse {
     robot: Point,
     map: Grid<Thing>,
 }
 
 impl Warehouse {
-    fn new(plan: &str) -> Warehouse {
+    fn new(plan: &str) -> Result<Warehouse, DayError> {
         // Here we iterate twice over plan - this not cost a meaningful amount of time
         let map = GridParser::new(|c| match c {
             '#' => Ok(Thing::Wall),
@@ -53,9 +101,14 @@ impl Warehouse {
             '@' => Ok(Thing::Floor),
             _ => Err(GridParseError),
         })
-        .parse(plan)
-        .unwrap();
-        Warehouse {
+        .parse(plan)?;
+        // moves only check for walls, not for the end of the map
+        if !map.is_enclosed(|t| *t == Thing::Wall) {
+            return Err(DayError::WrongFormat(
+                "the warehouse needs a wall around it",
+            ));
+        }
+        Ok(Warehouse {
             robot: plan
                 .lines()
                 .flat_map(|l| l.chars())
@@ -65,9 +118,9 @@ impl Warehouse {
                     _ => None,
                 })
                 .next()
-                .unwrap(),
+                .ok_or(DayError::WrongFormat("no robot in the warehouse"))?,
             map,
-        }
+        })
     }
 
     fn do_move(&mut self, movement: Direction) {
@@ -186,28 +239,27 @@ impl Warehouse2 {
     }
 }
 
-// no error handling ¯\_(ツ)_/¯
-fn parse(input: &str) -> (Warehouse, Vec<Direction>) {
-    fn from_arrow(a: char) -> Direction {
+fn parse(input: &str) -> Result<(Warehouse, Vec<Direction>), DayError> {
+    fn from_arrow(a: char) -> Result<Direction, DayError> {
         match a {
-            '^' => Direction::Up,
-            '>' => Direction::Right,
-            'v' => Direction::Down,
-            '<' => Direction::Left,
-            _ => panic!("Not an arrow"),
+            '^' => Ok(Direction::Up),
+            '>' => Ok(Direction::Right),
+            'v' => Ok(Direction::Down),
+            '<' => Ok(Direction::Left),
+            _ => Err(DayError::WrongFormat("not an arrow")),
         }
     }
     let mut blocks = input.split("\n\n");
     if let (Some(warehouse_plan), Some(movements)) = (blocks.next(), blocks.next()) {
-        return (
-            Warehouse::new(warehouse_plan),
+        return Ok((
+            Warehouse::new(warehouse_plan)?,
             movements
                 .lines()
                 .flat_map(|l| l.chars().map(from_arrow))
-                .collect(),
-        );
+                .collect::<Result<_, _>>()?,
+        ));
     }
-    panic!("Input looks unexpected");
+    Err(DayError::WrongFormat("expected a warehouse and movements"))
 }
 
 impl fmt::Display for Warehouse2 {
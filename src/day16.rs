@@ -1,39 +1,93 @@
-use itertools::Itertools;
+use itertools::{iproduct, Itertools};
 
 use crate::{
+    generate::{self, Rng},
     graph::{dijkstra, dijkstra_all},
     grid::{Direction, Grid, GridParseError, GridParser, Index},
+    params::Params,
+    solution::{Answer, DayError, Solution},
     util::IteratorExt,
 };
 
-pub fn solve(input: &str) -> (usize, usize) {
-    let (start, end, map) = parse(input).unwrap();
-
-    // println!(
-    //     "{}",
-    //     map.display(
-    //         |t, _| match t {
-    //             Thing::Wall => '#',
-    //             Thing::Floor => '.',
-    //         }
-    //     )
-    // );
-
-    let (end_dir, cost) = part1(start.clone(), end, &map);
-    let count_on_best_path = part2(
-        start,
-        Pose {
-            position: end,
-            heading: end_dir,
-        },
-        &map,
-    );
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = (Pose, Index, Grid<Thing>);
+
+    fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+        parse(input)
+    }
+
+    fn part1(&self, (start, end, map): &Self::Input) -> Result<Answer, DayError> {
+        // println!(
+        //     "{}",
+        //     map.display(
+        //         |t, _| match t {
+        //             Thing::Wall => '#',
+        //             Thing::Floor => '.',
+        //         }
+        //     )
+        // );
+
+        let (_, cost) = part1(start.clone(), *end, map)?;
+        Ok(cost.into())
+    }
+
+    fn part2(&self, (start, end, map): &Self::Input) -> Result<Answer, DayError> {
+        // part 2 needs to know from which direction the best path reaches the end,
+        // the first heading the search reaches the end with is the one of a cheapest path
+        let end = dijkstra(start.clone(), |p| movements(p, map), |p| p.position == *end)
+            .and_then(|path| path.last())
+            .ok_or(DayError::NoSolution)?;
+        Ok(part2(start.clone(), end, map).into())
+    }
 
-    (cost, count_on_best_path)
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Some(random_input(size, rng))
+    }
+}
+
+// a maze of size times size, rounded up to odd, with S at the bottom left and E at the top right.
+// Some walls are removed so that there are several best paths
+fn random_input(size: usize, rng: &mut Rng) -> String {
+    let n = size.max(5) | 1;
+    let (start, end) = ((n - 2, 1), (1, n - 2));
+    let mut open = vec![vec![false; n]; n];
+    open[start.0][start.1] = true;
+    // depth first, cells are at odd positions and the walls between them at mixed ones
+    let mut stack = vec![start];
+    while let Some(&(row, col)) = stack.last() {
+        let next = [(0, 2), (2, 0), (0, -2), (-2, 0)]
+            .into_iter()
+            .map(|(dr, dc)| (row as isize + dr, col as isize + dc))
+            .filter(|&(r, c)| r > 0 && c > 0 && r < n as isize - 1 && c < n as isize - 1)
+            .map(|(r, c)| (r as usize, c as usize))
+            .filter(|&(r, c)| !open[r][c])
+            .collect_vec();
+        if next.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let (r, c) = rng.pick(&next);
+        open[(row + r) / 2][(col + c) / 2] = true;
+        open[r][c] = true;
+        stack.push((r, c));
+    }
+    for (row, col) in iproduct!(1..n - 1, 1..n - 1) {
+        if (row + col) % 2 == 1 && rng.chance(0.1) {
+            open[row][col] = true;
+        }
+    }
+    generate::grid(n, n, |row, col| match (row, col) {
+        p if p == start => 'S',
+        p if p == end => 'E',
+        (row, col) if open[row][col] => '.',
+        _ => '#',
+    })
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
-struct Pose {
+pub struct Pose {
     position: Index,
     heading: Direction,
 }
@@ -48,12 +102,12 @@ impl Ord for Pose {
 
 impl PartialOrd for Pose {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Copy)]
-enum Thing {
+pub enum Thing {
     Wall,
     Floor,
 }
@@ -65,7 +119,7 @@ fn cost(path: &[Pose]) -> usize {
         .sum()
 }
 
-fn part1(start: Pose, end: Index, map: &Grid<Thing>) -> (Direction, usize) {
+fn part1(start: Pose, end: Index, map: &Grid<Thing>) -> Result<(Direction, usize), DayError> {
     // need to check all 4 possible end-headings
     Direction::all()
         .iter()
@@ -73,17 +127,13 @@ fn part1(start: Pose, end: Index, map: &Grid<Thing>) -> (Direction, usize) {
             position: end,
             heading: d,
         })
-        .map(|e| {
-            (
-                e.heading,
-                dijkstra(start.clone(), |p| movements(p, map), |n| *n == e)
-                    .unwrap() // we're sure there's a way to the end
-                    .collect::<Vec<_>>(),
-            )
+        .filter_map(|e| {
+            dijkstra(start.clone(), |p| movements(p, map), |n| *n == e)
+                .map(|path| (e.heading, path.collect::<Vec<_>>()))
         })
         .map(|(dir, path)| (dir, cost(&path)))
         .min_by_key(|(_, cost)| *cost)
-        .unwrap() // we know the iterator is not empty
+        .ok_or(DayError::NoSolution) // no way to the end
 }
 
 fn movements(pose: &Pose, map: &Grid<Thing>) -> impl IntoIterator<Item = (usize, Pose)> {
@@ -115,7 +165,7 @@ fn movements(pose: &Pose, map: &Grid<Thing>) -> impl IntoIterator<Item = (usize,
         )),
     ]
     .into_iter()
-    .filter_map(|p| p)
+    .flatten()
 }
 
 fn part2(start: Pose, end: Pose, map: &Grid<Thing>) -> usize {
@@ -127,7 +177,7 @@ fn part2(start: Pose, end: Pose, map: &Grid<Thing>) -> usize {
         .count()
 }
 
-fn parse(input: &str) -> Result<(Pose, Index, Grid<Thing>), GridParseError> {
+fn parse(input: &str) -> Result<(Pose, Index, Grid<Thing>), DayError> {
     let map = GridParser::new(|c| match c {
         '#' => Ok(Thing::Wall),
         '.' => Ok(Thing::Floor),
@@ -136,6 +186,10 @@ fn parse(input: &str) -> Result<(Pose, Index, Grid<Thing>), GridParseError> {
         _ => Err(GridParseError),
     })
     .parse(input)?;
+    // neighbors are only checked for walls, not for the end of the map
+    if !map.is_enclosed(|t| *t == Thing::Wall) {
+        return Err(DayError::WrongFormat("the maze needs a wall around it"));
+    }
 
     let start = input
         .chars()
@@ -1,10 +1,72 @@
-use crate::graph::dijkstra;
+use itertools::Itertools;
 
-pub fn solve(_: &str) -> (usize, usize) {
-    (
-        to_usize_digits(&execute1(17323786)),
-        inverse(&[2, 4, 1, 1, 7, 5, 1, 5, 4, 1, 5, 5, 0, 3, 3, 0]),
-    )
+use crate::{
+    graph::dijkstra,
+    params::{Param, ParamKind, Params},
+    solution::{Answer, DayError, Solution},
+};
+
+// the variants differ in how the decompiled program is run for part 1
+pub struct Day17 {
+    execute: fn(usize) -> Vec<u8>,
+}
+
+impl Day17 {
+    pub const EXECUTE1: Day17 = Day17 { execute: execute1 };
+    pub const EXECUTE2: Day17 = Day17 { execute: execute2 };
+    pub const EXECUTE3: Day17 = Day17 { execute: execute3 };
+}
+
+static PARAMS: &[Param] = &[
+    Param {
+        name: "a",
+        kind: ParamKind::Unsigned {
+            min: 0,
+            max: u32::MAX as u64,
+        },
+        default: "17323786",
+        help: "Initial value of register A for part 1",
+    },
+    Param {
+        name: "program",
+        kind: ParamKind::List { max: 7 },
+        default: "2,4,1,1,7,5,1,5,4,1,5,5,0,3,3,0",
+        help: "Output part 2 searches for, the decompiled program is fixed",
+    },
+];
+
+// the input is not read, the program was decompiled by hand (see `execute1`)
+impl Solution for Day17 {
+    type Input = (usize, Vec<u8>);
+
+    fn params(&self) -> &'static [Param] {
+        PARAMS
+    }
+
+    fn parse(&self, _: &str, params: &Params) -> Result<Self::Input, DayError> {
+        // both fit, the kinds are bounded
+        let program: Vec<_> = params
+            .list("program")
+            .into_iter()
+            .map(|d| d as u8)
+            .collect();
+        // a needs 3 bits per digit, plus the look-ahead
+        if program.len() > 19 {
+            return Err(DayError::WrongFormat("program has more than 19 digits"));
+        }
+        Ok((params.unsigned("a") as usize, program))
+    }
+
+    fn part1(&self, (a, _): &Self::Input) -> Result<Answer, DayError> {
+        // `execute` returns the most significant digit first, the machine outputs it last
+        Ok((self.execute)(*a).iter().rev().join(",").into())
+    }
+
+    fn part2(&self, (_, program): &Self::Input) -> Result<Answer, DayError> {
+        inverse(program)
+            .map(Answer::from)
+            .ok_or(DayError::NoSolution)
+    }
 }
 
 // by hand,
@@ -24,7 +86,6 @@ fn execute1(mut a: usize) -> Vec<u8> {
     res
 }
 
-#[allow(dead_code)]
 fn execute2(a: usize) -> Vec<u8> {
     let d = to_binary_digits(a);
     let get_3_bits_starting_at = |i| to_usize_binary(d.get(i..i + 3).unwrap_or(&[]));
@@ -40,7 +101,6 @@ fn execute2(a: usize) -> Vec<u8> {
         .collect()
 }
 
-#[allow(dead_code)]
 fn execute3(a: usize) -> Vec<u8> {
     (0..(to_binary_digits(a).len() as f32 / 3.0).ceil() as usize)
         .map(|i| {
@@ -63,7 +123,7 @@ fn replace_3_bits_starting_at(v: usize, i: usize, chunk: usize) -> usize {
 }
 
 // more like some pseudo-inverse because `execute` is not injective
-fn inverse(r: &[u8]) -> usize {
+fn inverse(r: &[u8]) -> Option<usize> {
     // strategy: build `a` in reverse
     // Because of the look-ahead, the value of the current digit of the output could
     // be generated by different values of the current digit of the input.
@@ -104,8 +164,8 @@ fn inverse(r: &[u8]) -> usize {
         },
         |&n| n.0.is_none(),
     )
-    .map(|s| s.last().unwrap().1)
-    .expect("No inverse found!")
+    .and_then(|s| s.last())
+    .map(|n| n.1)
 }
 
 // lsb
@@ -113,13 +173,6 @@ fn to_usize_binary(x: &[bool]) -> usize {
     x.iter().enumerate().map(|(i, &v)| (v as usize) << i).sum()
 }
 
-fn to_usize_digits(x: &[u8]) -> usize {
-    x.iter()
-        .enumerate()
-        .map(|(i, &v)| v as usize * 10usize.pow(i as u32))
-        .sum()
-}
-
 // lsb
 fn to_binary_digits(mut x: usize) -> Vec<bool> {
     let mut result = vec![];
@@ -0,0 +1,223 @@
+use std::{process::ExitCode, thread, time::Duration};
+
+use itertools::Itertools;
+
+use aoc24::{
+    bench::{BenchConfig, Until},
+    registry::{Day, Variant},
+    runner::{self, Solved},
+    solution::DayError,
+};
+
+use crate::{params_for, read_day, show, Args};
+
+// solves every variant repeatedly with one thread and with many,
+// fails if the answers of a variant are not always the same
+pub fn run(selected: &[(&'static Day, Vec<&'static Variant>)], args: &Args) -> ExitCode {
+    let threads = args
+        .threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let repeats = args.iterations().unwrap_or(5).max(1);
+    let pools = [1, threads].map(|n| rayon::ThreadPoolBuilder::new().num_threads(n).build());
+    let [Ok(single), Ok(multi)] = pools else {
+        println!("Cannot start {} threads", threads);
+        return ExitCode::FAILURE;
+    };
+    let once = BenchConfig {
+        warmup: 0,
+        until: Until::Iterations(1),
+    };
+
+    println!(
+        "{:<16} {:>12} {:>12} {:>8}  answers",
+        "day",
+        "1 thread",
+        format!("{} threads", threads),
+        "speedup"
+    );
+    let mut ok = true;
+    for (day, variants) in selected {
+        let (_, read) = read_day(day, &args.input_base, args);
+        let (input, header) = match &read {
+            Ok(read) => read,
+            Err(e) => {
+                ok = false;
+                println!("{:<16} FAILED: {}", day.label, e);
+                continue;
+            }
+        };
+        for variant in variants {
+            let label = day.label_of(variant);
+            let runs = |pool| {
+                repeat_on(pool, repeats, || {
+                    params_for(day, variant, header, args).and_then(|params| {
+                        runner::solve(variant.solution, input, &params, &once, args.part)
+                    })
+                })
+            };
+            let (single, multi) = (runs(&single), runs(&multi));
+            match consistency(&single, &multi, threads) {
+                Consistency::Same(answers) => {
+                    let median = |runs: &[(_, Duration)]| {
+                        let times = runs.iter().map(|(_, time)| *time).sorted().collect_vec();
+                        times[times.len() / 2]
+                    };
+                    let (one, many) = (median(&single), median(&multi));
+                    println!(
+                        "{:<16} {:>12} {:>12} {:>7.2}x  {}",
+                        label,
+                        format!("{:.2?}", one),
+                        format!("{:.2?}", many),
+                        one.as_secs_f64() / many.as_secs_f64(),
+                        answers
+                    );
+                }
+                Consistency::Failed(e) => {
+                    ok = false;
+                    println!("{:<16} FAILED: {}", label, e);
+                }
+                Consistency::Differ(each) => {
+                    ok = false;
+                    println!("{:<16} NONDETERMINISTIC: {}", label, each);
+                }
+            }
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+// answers or error, and the time of one run, for every run of solve on the pool
+fn repeat_on(
+    pool: &rayon::ThreadPool,
+    repeats: usize,
+    solve: impl Fn() -> Result<Solved, DayError> + Sync,
+) -> Vec<(Result<String, String>, Duration)> {
+    (0..repeats)
+        .map(|_| match pool.install(&solve) {
+            Ok(Solved {
+                answers, timings, ..
+            }) => (
+                Ok(show(&answers)),
+                timings.iter().map(|(_, stats)| stats.mean).sum(),
+            ),
+            Err(e) => (Err(e.to_string()), Duration::ZERO),
+        })
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum Consistency {
+    // the answers of every run
+    Same(String),
+    // the error of every run
+    Failed(String),
+    // every distinct outcome and the number of threads it came from
+    Differ(String),
+}
+
+fn consistency(
+    single: &[(Result<String, String>, Duration)],
+    multi: &[(Result<String, String>, Duration)],
+    threads: usize,
+) -> Consistency {
+    let distinct = single.iter().chain(multi).map(|(a, _)| a).unique();
+    match distinct.collect_vec()[..] {
+        [Ok(answers)] => Consistency::Same(answers.clone()),
+        [Err(e)] => Consistency::Failed(e.clone()),
+        _ => Consistency::Differ(
+            single
+                .iter()
+                .map(|(a, _)| (1, a))
+                .chain(multi.iter().map(|(a, _)| (threads, a)))
+                .unique()
+                .map(|(n, a)| {
+                    let a = a
+                        .as_ref()
+                        .map_or_else(|e| format!("FAILED {}", e), String::clone);
+                    format!("{} with {} threads", a, n)
+                })
+                .join(", "),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use aoc24::{
+        params::Params,
+        solution::{Answer, Solution},
+    };
+
+    use super::*;
+
+    // a different answer for part 1 every time it is solved
+    struct Counter(AtomicU64);
+
+    impl Solution for Counter {
+        type Input = ();
+
+        fn parse(&self, _: &str, _: &Params) -> Result<Self::Input, DayError> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &Self::Input) -> Result<Answer, DayError> {
+            Ok(Answer::Unsigned(self.0.fetch_add(1, Ordering::Relaxed)))
+        }
+
+        fn part2(&self, _: &Self::Input) -> Result<Answer, DayError> {
+            Ok(Answer::Unsigned(7))
+        }
+    }
+
+    #[test]
+    fn test_determinism() {
+        let pool = |n| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(n)
+                .build()
+                .unwrap()
+        };
+        let (single, multi) = (pool(1), pool(2));
+        let once = BenchConfig {
+            warmup: 0,
+            until: Until::Iterations(1),
+        };
+        let counter = Counter(AtomicU64::new(0));
+        let runs = |pool, part| {
+            repeat_on(pool, 2, || {
+                runner::solve(&counter, "", &Params::default(), &once, part)
+            })
+        };
+
+        let (one, many) = (runs(&single, Some(2)), runs(&multi, Some(2)));
+        assert_eq!(
+            consistency(&one, &many, 2),
+            Consistency::Same("(-, 7)".to_owned())
+        );
+        let (one, many) = (runs(&single, Some(1)), runs(&multi, Some(1)));
+        assert_eq!(
+            consistency(&one, &many, 2),
+            Consistency::Differ(
+                "(0, -) with 1 threads, (1, -) with 1 threads, \
+                 (2, -) with 2 threads, (3, -) with 2 threads"
+                    .to_owned()
+            )
+        );
+        let failed = [(Err("No solution found".to_owned()), Duration::ZERO)];
+        assert_eq!(
+            consistency(&failed, &failed, 2),
+            Consistency::Failed("No solution found".to_owned())
+        );
+        assert!(matches!(
+            consistency(&one, &failed, 2),
+            Consistency::Differ(_)
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    bench::{BenchConfig, Until},
+    params::{self, Params},
+    registry::{self, Day},
+    runner,
+    solution::{Answer, DayError},
+};
+
+/// An example input, `dayNN_test*.txt`, with the answers it should give.
+///
+/// Expectations and parameters go into a header that is ended by a line `---`,
+/// files without that line are all input and have nothing to check:
+///
+/// ```text
+/// part1: 12
+/// param: width=11
+/// param: height=7
+/// ---
+/// p=0,4 v=3,-3
+/// ```
+#[derive(Debug)]
+pub struct Example {
+    pub path: PathBuf,
+    pub day: u8,
+    pub header: Header,
+    pub input: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Header {
+    /// part1 and part2, compared by their string representation
+    pub expected: [Option<String>; 2],
+    pub params: Vec<(String, String)>,
+}
+
+/// How one part of an example turned out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass(String),
+    Fail { expected: String, actual: String },
+    // the header has no answer for this part
+    Unchecked(String),
+}
+
+/// Both parts of an example solved by one variant
+pub type Outcome = Result<[Verdict; 2], DayError>;
+
+/// Files named `dayNN_test*.txt` in dir, sorted by name
+pub fn discover(dir: &Path) -> Result<Vec<PathBuf>, DayError> {
+    let io_error = |source| DayError::Io {
+        path: dir.display().to_string(),
+        source,
+    };
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| day_of(n).is_some())
+        {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+// "day14_test_small.txt" is an example of day 14
+fn day_of(file_name: &str) -> Option<u8> {
+    let rest = file_name.strip_prefix("day")?.strip_suffix(".txt")?;
+    let (day, suffix) = rest.split_at_checked(2)?;
+    if !suffix.starts_with("_test") || !day.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    day.parse().ok()
+}
+
+/// Splits text into its header and the input below it
+pub fn split_header(text: &str) -> Result<(Header, &str), DayError> {
+    let Some((header, input)) = text
+        .split_once("---\n")
+        .filter(|(header, _)| header.is_empty() || header.ends_with('\n'))
+    else {
+        return Ok((Header::default(), text));
+    };
+    let mut parsed = Header::default();
+    for line in header.lines().filter(|l| !l.trim().is_empty()) {
+        let wrong = || DayError::ExampleHeader(line.to_owned());
+        let (key, value) = line.split_once(':').ok_or_else(wrong)?;
+        let value = value.trim().to_owned();
+        match key.trim() {
+            "part1" => parsed.expected[0] = Some(value),
+            "part2" => parsed.expected[1] = Some(value),
+            "param" => parsed.params.push(params::parse_assignment(&value)?),
+            _ => return Err(wrong()),
+        }
+    }
+    Ok((parsed, input))
+}
+
+impl Example {
+    pub fn load(path: &Path) -> Result<Example, DayError> {
+        let day = path
+            .file_name()
+            .and_then(|n| day_of(n.to_str()?))
+            .ok_or(DayError::WrongFormat("examples are named dayNN_test*.txt"))?;
+        let text = runner::read_input(&path.display().to_string())?;
+        let (header, input) = split_header(&text)?;
+        Ok(Example {
+            path: path.to_owned(),
+            day,
+            header,
+            input: input.to_owned(),
+        })
+    }
+
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or(String::new(), |n| n.to_string_lossy().into_owned())
+    }
+
+    /// Solves the example once with every variant of its day, labelled like "day11-digits"
+    pub fn run(&self) -> Result<Vec<(String, Outcome)>, DayError> {
+        let day: &Day = registry::get(self.day).ok_or(DayError::UnknownDay(self.day))?;
+        let once = BenchConfig {
+            warmup: 0,
+            until: Until::Iterations(1),
+        };
+        Ok(day
+            .variants
+            .iter()
+            .map(|variant| {
+                let verdicts = Params::resolve(variant.solution.params(), &self.header.params)
+                    .map_err(DayError::from)
+                    .and_then(|params| {
+                        runner::solve(variant.solution, &self.input, &params, &once, None)
+                    })
+                    .map(|solved| {
+                        let (part1, part2) = solved.answers;
+                        [self.verdict(0, part1), self.verdict(1, part2)]
+                    });
+                (day.label_of(variant), verdicts)
+            })
+            .collect())
+    }
+
+    fn verdict(&self, part: usize, answer: Option<Answer>) -> Verdict {
+        let actual = answer.map_or(String::new(), |a| a.to_string());
+        match &self.header.expected[part] {
+            None => Verdict::Unchecked(actual),
+            Some(expected) if *expected == actual => Verdict::Pass(actual),
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_of() {
+        assert_eq!(day_of("day14_test.txt"), Some(14));
+        assert_eq!(day_of("day03_test_part1.txt"), Some(3));
+        assert_eq!(day_of("day14.txt"), None);
+        assert_eq!(day_of("day14_test.csv"), None);
+        assert_eq!(day_of("answers_test.toml"), None);
+    }
+
+    #[test]
+    fn test_split_header() {
+        let (header, input) =
+            split_header("part1: 12\nparam: width=11\n---\np=0,4 v=3,-3\n").unwrap();
+        assert_eq!(header.expected, [Some("12".to_owned()), None]);
+        assert_eq!(header.params, vec![("width".to_owned(), "11".to_owned())]);
+        assert_eq!(input, "p=0,4 v=3,-3\n");
+
+        // no header
+        let (header, input) = split_header("125 17\n").unwrap();
+        assert_eq!(header, Header::default());
+        assert_eq!(input, "125 17\n");
+
+        assert!(split_header("part3: 1\n---\n").is_err());
+        assert!(split_header("param: width\n---\n").is_err());
+    }
+}
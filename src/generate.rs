@@ -0,0 +1,130 @@
+//! Random puzzle inputs for stress tests, see [`Solution::generate`](crate::solution::Solution::generate).
+//!
+//! The same day, size and seed always give the same input, also across versions of the crate,
+//! so a slow or failing input can be reproduced from the command line that generated it.
+
+use crate::{registry, solution::DayError};
+
+/// splitmix64, small and good enough for inputs. Not `rand`, whose generators may change
+/// between versions and with them every generated input.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in 0..n, n needs to be positive
+    pub fn below(&mut self, n: u64) -> u64 {
+        // the bias is at most n / 2^64
+        self.next_u64() % n
+    }
+
+    /// Uniform in low..=high
+    pub fn between(&mut self, low: i64, high: i64) -> i64 {
+        low + self.below(high.abs_diff(low) + 1) as i64
+    }
+
+    /// True with probability p
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    pub fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i as u64 + 1) as usize);
+        }
+    }
+}
+
+/// Lines of a grid with rows lines of cols characters, cell gets (row, col)
+pub fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    let mut s = String::with_capacity(rows * (cols + 1));
+    for row in 0..rows {
+        s.extend((0..cols).map(|col| cell(row, col)));
+        s.push('\n');
+    }
+    s
+}
+
+/// An input of the default implementation of day, None if the day has no generator
+pub fn input(day: u8, size: usize, seed: u64) -> Result<Option<String>, DayError> {
+    let day = registry::get(day).ok_or(DayError::UnknownDay(day))?;
+    let variant = day.variant(None).expect("Every day has a default");
+    Ok(variant.solution.generate(size, &mut Rng::new(seed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+
+    #[test]
+    fn test_rng() {
+        let mut rng = Rng::new(7);
+        let first: Vec<_> = (0..5).map(|_| rng.next_u64()).collect();
+        let mut again = Rng::new(7);
+        assert!(first.iter().all(|&n| n == again.next_u64()));
+        assert_ne!(first[0], Rng::new(8).next_u64());
+
+        for _ in 0..100 {
+            assert!((-3..=3).contains(&rng.between(-3, 3)));
+        }
+        let mut items = [1, 2, 3, 4, 5];
+        rng.shuffle(&mut items);
+        items.sort();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+    }
+
+    // every generated input needs to parse and solve with every implementation of its day,
+    // a part may find no solution in a random input but must not fail otherwise
+    #[test]
+    fn test_inputs_solve() {
+        for day in registry::DAYS {
+            for (size, seed) in [(1, 0), (2, 4), (5, 1), (12, 2), (20, 3), (100, 5)] {
+                let Some(input) = input(day.day, size, seed).unwrap() else {
+                    continue;
+                };
+                assert_eq!(input, super::input(day.day, size, seed).unwrap().unwrap());
+                for variant in day.variants {
+                    let fail = |e: DayError| -> ! {
+                        panic!(
+                            "{} size {} seed {}: {}\n{}",
+                            day.label_of(variant),
+                            size,
+                            seed,
+                            e,
+                            input
+                        )
+                    };
+                    let params = Params::defaults(variant.solution.params());
+                    let parsed = variant
+                        .solution
+                        .parse(&input, &params)
+                        .unwrap_or_else(|e| fail(e));
+                    for answer in [
+                        variant.solution.part1(&parsed),
+                        variant.solution.part2(&parsed),
+                    ] {
+                        match answer {
+                            Ok(_) | Err(DayError::NoSolution) => {}
+                            Err(e) => fail(e),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,8 @@ use std::{
     ops::Add,
 };
 
+/// Lazily visits every node reachable from the start nodes in breadth-first order,
+/// `next` gives the neighbors of a node. Every node is visited once.
 pub fn bfs<F, N, U>(start: U, next: F) -> Bfs<F, N>
 where
     N: Eq + Hash + Clone,
@@ -21,6 +23,7 @@ where
     }
 }
 
+/// Iterator returned by [`bfs`]
 pub struct Bfs<F, N> {
     fun: F,
     todo: VecDeque<N>,
@@ -36,13 +39,12 @@ where
     type Item = N;
     fn next(&mut self) -> Option<Self::Item> {
         let next = self.todo.pop_back();
-        next.map(|t| {
-            for n in (&mut self.fun)(&t) {
+        next.inspect(|t| {
+            for n in (self.fun)(t) {
                 if self.done.insert(n.clone()) {
                     self.todo.push_front(n);
                 }
             }
-            t
         })
     }
 }
@@ -74,7 +76,7 @@ where
             new_walk.push(neighbor.clone());
             if neighbor == end {
                 all_walks.push(new_walk);
-            } else if new_walk.len() + 1 <= max_len {
+            } else if new_walk.len() < max_len {
                 queue.push_back((neighbor, new_walk));
             }
         }
@@ -143,6 +145,8 @@ impl Ord for Cost {
     }
 }
 
+/// A cheapest path from start to the first node for which `end` is true, including both.
+/// `neighbors` gives (edge cost, neighbor) pairs. None if no end node is reachable.
 pub fn dijkstra<N, F, U, P>(
     start: N,
     mut neighbors: F,
@@ -201,7 +205,7 @@ where
     None
 }
 
-// same as dijkstra, but returns all shortest paths instead of just one
+/// Same as [`dijkstra`], but returns all cheapest paths to a single end node instead of just one
 pub fn dijkstra_all<N, F, U>(start: N, mut neighbors: F, end: N) -> Vec<Vec<N>>
 where
     // I don't like that we need Ord, but as a tiebreaker for the heap it seems necessary
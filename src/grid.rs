@@ -6,9 +6,12 @@ use std::{
 };
 
 use itertools::{IntoChunks, Itertools};
+use thiserror::Error;
 
+/// Width and height of a grid
 pub type Size = (usize, usize);
 
+/// Position of a cell as (column, row), rows count downwards from the top
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Index((usize, usize));
 
@@ -20,6 +23,7 @@ fn dyadic(size: Size, index: usize) -> Index {
     Index((index % size.0, index / size.0))
 }
 
+/// The four directions between neighboring cells, `Up` is towards the first row
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
     Up,
@@ -29,6 +33,7 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// All directions in clockwise order, starting with `Up`
     pub fn all() -> [Direction; 4] {
         static DIRECTIONS: [Direction; 4] = [
             Direction::Up,
@@ -36,7 +41,7 @@ impl Direction {
             Direction::Down,
             Direction::Left,
         ];
-        DIRECTIONS.clone()
+        DIRECTIONS
     }
 
     pub fn clockwise(self) -> Self {
@@ -59,6 +64,11 @@ impl Direction {
 }
 
 impl Index {
+    pub fn new(column: usize, row: usize) -> Index {
+        Index((column, row))
+    }
+
+    /// The adjacent cell, panics when stepping over the first row or column
     pub fn neighbor(self, dir: Direction) -> Index {
         let (x, y) = self.into();
         match dir {
@@ -70,9 +80,9 @@ impl Index {
     }
 }
 
-impl Into<Vector> for Direction {
-    fn into(self) -> Vector {
-        match self {
+impl From<Direction> for Vector {
+    fn from(dir: Direction) -> Vector {
+        match dir {
             Direction::Up => Vector::new([0, 1]),
             Direction::Right => Vector::new([1, 0]),
             Direction::Down => Vector::new([0, -1]),
@@ -81,6 +91,7 @@ impl Into<Vector> for Direction {
     }
 }
 
+/// Rectangular grid stored row by row
 #[derive(Debug, Clone)]
 pub struct Grid<T> {
     pub size: Size,
@@ -88,6 +99,7 @@ pub struct Grid<T> {
 }
 
 impl<T> Grid<T> {
+    /// Panics if there are not exactly width * height elements
     pub fn new(size: Size, elements: Vec<T>) -> Grid<T> {
         if size.0 * size.1 != elements.len() {
             panic!("Mismatched size");
@@ -95,6 +107,7 @@ impl<T> Grid<T> {
         Grid { size, elements }
     }
 
+    /// None outside of the grid
     pub fn at(&self, idx: Index) -> Option<&T> {
         if idx.0 .0 < self.size.0 && idx.0 .1 < self.size.1 {
             Some(&self[idx])
@@ -103,6 +116,7 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Same as `at`, for a point in the coordinate system of the grid
     pub fn at_point(&self, pos: Point) -> Option<&T> {
         pos.try_into().ok().and_then(|idx: Index| self.at(idx))
     }
@@ -112,33 +126,54 @@ impl<T> Grid<T> {
             .swap(monadic(self.size, a), monadic(self.size, b));
     }
 
+    /// All indices in storage order
     pub fn iter_indices(&self) -> impl Iterator<Item = Index> {
         let size = self.size;
         (0..self.size.0 * self.size.1).map(move |i| dyadic(size, i))
     }
 
+    /// All indices, grouped into chunks of one row each
     pub fn iter_indices_by_rows(&self) -> IntoChunks<impl Iterator<Item = Index>> {
         let rows = self.size.0;
         self.iter_indices().chunks(rows)
     }
 
+    /// Whether every cell in the first and last row and column is a wall, so that stepping
+    /// to a neighbor never leaves the grid from the inside. False for an empty grid
+    pub fn is_enclosed(&self, wall: impl Fn(&T) -> bool) -> bool {
+        let (width, height) = self.size;
+        width > 0
+            && height > 0
+            && self
+                .iter_indices()
+                .filter(|&i| {
+                    let (x, y) = i.into();
+                    x == 0 || y == 0 || x == width - 1 || y == height - 1
+                })
+                .all(|i| wall(&self[i]))
+    }
+
+    /// The index of the n-th element in storage order
     pub fn make_index(&self, monadic: usize) -> Index {
         dyadic(self.size, monadic)
     }
 
+    /// Displays the grid with one char per cell as given by mapping
     pub fn display<'a, M>(&'a self, mapping: M) -> GridDisplayer<'a, M, T>
     where
         M: Fn(&T, Index) -> char,
     {
-        GridDisplayer::new(mapping, &self)
+        GridDisplayer::new(mapping, self)
     }
 }
 
+/// Parses lines of equal length into a grid, mapping every char to a cell
 pub struct GridParser<M> {
     mapping: M,
 }
 
-#[derive(Debug)]
+#[derive(Error, Debug)]
+#[error("Unexpected character or lines of different length")]
 pub struct GridParseError;
 
 impl<M: FnMut(char) -> Result<T, GridParseError>, T> GridParser<M> {
@@ -152,6 +187,9 @@ impl<M: FnMut(char) -> Result<T, GridParseError>, T> GridParser<M> {
             return Ok(Grid::new((0, 0), Vec::new()));
         }
         let size = (lines[0].len(), lines.len());
+        if lines.iter().any(|l| l.len() != size.0) {
+            return Err(GridParseError);
+        }
         let things: Vec<_> = lines
             .into_iter()
             .flat_map(|v| v.chars())
@@ -227,9 +265,12 @@ impl<T> IndexMut<Index> for Grid<T> {
 
 pub type Signed = i32;
 
+/// Position as (x, y) with y pointing up, so the first row of a grid is y = 0 and
+/// the rows below have negative y
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point(Coords);
 
+/// Difference between two points
 #[derive(Debug, Clone, Copy)]
 pub struct Vector(Coords);
 
@@ -241,15 +282,23 @@ impl Point {
         Point(Coords(coords))
     }
 
+    pub fn coords(self) -> [Signed; 2] {
+        self.0 .0
+    }
+
     pub fn scaled_x(self, factor: Signed) -> Self {
         Self::new([self.0 .0[0] * factor, self.0 .0[1]])
     }
 }
 
 impl Vector {
-    fn new(coords: [Signed; 2]) -> Self {
+    pub fn new(coords: [Signed; 2]) -> Self {
         Vector(Coords(coords))
     }
+
+    pub fn coords(self) -> [Signed; 2] {
+        self.0 .0
+    }
 }
 
 impl TryFrom<Index> for Coords {
@@ -269,7 +318,7 @@ impl TryFrom<Coords> for Index {
 impl TryFrom<Point> for Index {
     type Error = TryFromIntError;
     fn try_from(value: Point) -> Result<Self, Self::Error> {
-        Ok(value.0.try_into()?)
+        value.0.try_into()
     }
 }
 
@@ -280,6 +329,12 @@ impl TryFrom<Index> for Point {
     }
 }
 
+impl From<(usize, usize)> for Index {
+    fn from(value: (usize, usize)) -> Self {
+        Index(value)
+    }
+}
+
 impl From<Index> for (usize, usize) {
     fn from(value: Index) -> Self {
         value.0
@@ -0,0 +1,133 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+
+use aoc24::{bench::Stats, solution::Phase};
+
+// Every bench run appends one line per day and phase to a csv file in the input directory.
+// All lines of one run share the timestamp (seconds since the epoch), durations are in nanoseconds.
+const HEADER: &str = "timestamp,day,phase,samples,min,median,mean,stddev,outliers";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub label: String,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+#[derive(Error, Debug)]
+pub enum HistoryError {
+    #[error("Cannot access history file: {0}")]
+    Io(#[from] io::Error),
+    #[error("History file has unexpected line {0}: {1}")]
+    WrongFormat(usize, String),
+}
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+pub fn append(path: &Path, records: &[Record]) -> Result<(), HistoryError> {
+    let new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if new {
+        writeln!(file, "{}", HEADER)?;
+    }
+    for record in records {
+        writeln!(file, "{}", record)?;
+    }
+    Ok(())
+}
+
+// a missing file is the same as an empty one
+pub fn load(path: &Path) -> Result<Vec<Record>, HistoryError> {
+    let content = match fs::read_to_string(path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| *line != HEADER && !line.is_empty())
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|_| HistoryError::WrongFormat(i + 1, line.to_owned()))
+        })
+        .collect()
+}
+
+impl std::fmt::Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = &self.stats;
+        write!(
+            f,
+            "{},{},{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.label,
+            self.phase,
+            s.samples,
+            s.min.as_nanos(),
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.stddev.as_nanos(),
+            s.outliers
+        )
+    }
+}
+
+impl std::str::FromStr for Record {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = s.split(',').collect();
+        let [timestamp, label, phase, samples, min, median, mean, stddev, outliers] = fields[..]
+        else {
+            return Err(());
+        };
+        let nanos = |s: &str| s.parse().map(Duration::from_nanos).map_err(|_| ());
+        Ok(Record {
+            timestamp: timestamp.parse().map_err(|_| ())?,
+            label: label.to_owned(),
+            phase: phase.parse().map_err(|_| ())?,
+            stats: Stats {
+                samples: samples.parse().map_err(|_| ())?,
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                stddev: nanos(stddev)?,
+                outliers: outliers.parse().map_err(|_| ())?,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let record = Record {
+            timestamp: 1733000000,
+            label: "day06".to_owned(),
+            phase: Phase::Part2,
+            stats: Stats::new(&[Duration::from_micros(1500), Duration::from_micros(1700)]),
+        };
+        let line = record.to_string();
+        assert!(line.starts_with("1733000000,day06,part2,2,1500000,1600000,"));
+        assert_eq!(line.parse(), Ok(record));
+        assert!("1733000000,day06,part3,1,1,1,1,0,0"
+            .parse::<Record>()
+            .is_err());
+        assert!("1733000000,day06".parse::<Record>().is_err());
+    }
+}
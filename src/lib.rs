@@ -0,0 +1,58 @@
+//! Solutions for Advent of Code 2024 and the helpers they are built from.
+//!
+//! [`grid`], [`graph`] and [`util`] do not know about any puzzle and can be used on their own.
+//! [`solve`] runs the default implementation of a day on an input:
+//!
+//! ```
+//! let (part1, part2) = aoc24::solve(1, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+//! assert_eq!((part1.to_string(), part2.to_string()), ("11".into(), "31".into()));
+//! ```
+
+#![feature(cmp_minmax)]
+#![feature(option_zip)]
+
+#[macro_use]
+extern crate static_assertions;
+
+pub mod util;
+pub mod grid;
+pub mod graph;
+pub mod solution;
+pub mod bench;
+pub mod runner;
+pub mod registry;
+pub mod params;
+pub mod examples;
+pub mod alloc;
+pub mod generate;
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+
+use params::Params;
+use solution::{Answer, DayError};
+
+/// Solves both parts of a day with its default implementation
+pub fn solve(day: u8, input: &str) -> Result<(Answer, Answer), DayError> {
+    let solution = registry::get(day)
+        .and_then(|d| d.variant(None))
+        .ok_or(DayError::UnknownDay(day))?
+        .solution;
+    let parsed = solution.parse(input, &Params::defaults(solution.params()))?;
+    Ok((solution.part1(&parsed)?, solution.part2(&parsed)?))
+}
@@ -1,110 +1,1129 @@
-#![feature(cmp_minmax)]
-#![feature(option_zip)]
-
-#[macro_use]
-extern crate static_assertions;
-
-use std::{fmt::Debug, fs, time::Instant};
-
-use clap::Parser;
-
-mod util;
-mod grid;
-mod graph;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
+use std::{
+    collections::BTreeMap,
+    io,
+    ops::RangeInclusive,
+    panic,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use answers::Answers;
+use aoc24::{
+    bench::{self, BenchConfig, Until},
+    examples::{self, Example, Verdict},
+    generate,
+    params::{self, Params},
+    registry::{self, Day, Variant},
+    runner::{self, Solved},
+    solution::{Answer, DayError},
+};
+use cache::Cache;
+use clap::{parser::ValueSource, ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use config::Config;
+use history::Record;
+use itertools::Itertools;
+use report::Format;
+
+// Human readable messages go to stderr when stdout is meant for other tools
+macro_rules! say {
+    ($format:expr, $($arg:tt)*) => {
+        if $format == Format::Text {
+            println!($($arg)*);
+        } else {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+mod answers;
+mod history;
+mod plot;
+mod report;
+mod batch;
+mod cache;
+mod config;
+mod determinism;
+mod matrix;
+mod scale;
+mod serve;
+mod watch;
 
 #[derive(Parser)]
 struct Args {
-    #[arg(short, long, default_value_t = 0)]
-    day: u8,
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Days to solve, e.g. 3,5,10-14 [default: all]
+    #[arg(
+        short,
+        long,
+        alias = "day",
+        global = true,
+        value_delimiter = ',',
+        value_parser = runner::parse_day_range
+    )]
+    days: Vec<RangeInclusive<u8>>,
+
+    /// Solve all days
+    #[arg(long, conflicts_with = "days")]
+    all: bool,
 
-    #[arg(short, long, default_value = "input")]
+    /// Only solve this part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+
+    /// Use this implementation instead of the default one, every selected day needs to have it
+    #[arg(long)]
+    variant: Option<String>,
+
+    /// Run every implementation of the selected days and check that they agree
+    #[arg(long, conflicts_with = "variant")]
+    cross_check: bool,
+
+    /// Change a puzzle constant of the selected days, e.g. width=11, can be repeated
+    #[arg(long = "param", value_name = "KEY=VALUE", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+
+    #[arg(short, long, global = true, default_value = "input")]
     input_base: String,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     test: bool,
+
+    /// Read the input of a single day from this file instead of the input directory, - for stdin
+    #[arg(long)]
+    input: Option<String>,
+
+    /// Compare the answers against the answers file in the input directory,
+    /// fails if an answer is not in the file unless --allow-unknown is given
+    #[arg(long, conflicts_with = "record")]
+    check: bool,
+
+    /// Let --check pass for answers that are not in the answers file
+    #[arg(long, requires = "check")]
+    allow_unknown: bool,
+
+    /// Write the answers into the answers file in the input directory
+    #[arg(long)]
+    record: bool,
+
+    /// How answers and timings are printed, json and csv have one record per day
+    /// with timings in nanoseconds
+    #[arg(long, value_enum, default_value = "text")]
+    format: Format,
+
+    /// Give up on a day after this long, for all runs of all phases, e.g. 2s for every day
+    /// or 6=30s for some days. Later budgets win, a day that times out fails the run
+    #[arg(long, value_name = "[DAYS=]DURATION", value_parser = runner::parse_budget)]
+    budget: Vec<(Option<RangeInclusive<u8>>, Duration)>,
+
+    /// Solve the days at the same time and print a table when all are done.
+    /// Days compete for the cores, so timings are higher than when run one by one
+    #[arg(long)]
+    parallel: bool,
+
+    /// Threads of the pool the solutions use for their parallel parts [default: one per core]
+    #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    threads: Option<usize>,
+
+    /// Solve every day --iterations times with one thread and with --threads threads,
+    /// report answers that differ and the speedup
+    #[arg(long, conflicts_with_all = ["parallel", "bench", "cache"])]
+    determinism: bool,
+
+    /// Solve every day against each of these input directories and print the answers and
+    /// timings side by side, * and ? match within a directory name, e.g. 'inputs/*'
+    #[arg(
+        long,
+        value_name = "DIRS",
+        num_args = 1..,
+        value_delimiter = ',',
+        conflicts_with_all = ["input", "check", "record", "bench", "determinism"]
+    )]
+    inputs: Vec<String>,
+
+    /// With --inputs, flag timings more than this many times the median of the day
+    #[arg(long, default_value = "3")]
+    outlier_factor: f64,
+
+    /// Solve again whenever one of the inputs changes, until interrupted
+    #[arg(long, conflicts_with_all = ["determinism", "inputs"])]
+    watch: bool,
+
+    /// Reuse answers of earlier runs if the input, parameters and binary are the same,
+    /// and remember new ones in target/answer_cache.json. Never used with --bench
+    #[arg(long)]
+    cache: bool,
+
+    /// Solve even if --cache is given
+    #[arg(long, overrides_with = "cache")]
+    no_cache: bool,
+
+    /// Forget all remembered answers before solving
+    #[arg(long)]
+    clear_cache: bool,
+
+    /// Report min, median, mean, stddev and outliers instead of only the average,
+    /// and append them to the benchmark history in the input directory
+    #[arg(long)]
+    bench: bool,
+
+    /// Untimed runs before measuring [default: 0, or 3 with --bench]
+    #[arg(long)]
+    warmup: Option<usize>,
+
+    /// Timed runs [default: 5, 1 with --test, or 30 with --bench]
+    #[arg(long, conflicts_with = "target_time")]
+    iterations: Option<usize>,
+
+    /// Repeat timed runs until this much time is spent per day, e.g. 2s or 500ms
+    #[arg(long, value_parser = bench::parse_duration)]
+    target_time: Option<Duration>,
+
+    /// Read defaults for the options from this file instead of aoc.toml in the working directory
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    // parameters of single days from the config file, --param is applied after them
+    #[arg(skip)]
+    day_params: BTreeMap<u8, Vec<(String, String)>>,
+
+    // timed runs from the config file, a default for benchmarks that doesn't turn requests
+    // of serve and batch into benchmarks like --iterations does
+    #[arg(skip)]
+    config_iterations: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Render the benchmark history as a trend chart per day and a bar chart of all days
+    Plot {
+        /// Directory the charts are written to
+        #[arg(short, long, default_value = "plots")]
+        out: PathBuf,
+
+        #[arg(short, long, value_enum, default_value = "svg")]
+        format: plot::Format,
+    },
+    /// Solve every example dayNN_test*.txt in the input directory with all variants
+    /// and compare against the answers in its header
+    Examples,
+    /// Answer POST /day/{n} with the request body as input, e.g.
+    /// `curl --data-binary @input/day14.txt 'localhost:8024/day/14?part=1&width=11'`.
+    /// --budget applies to every request, which is solved once unless --iterations,
+    /// --target-time or --bench ask for more. A solution that runs out of its budget keeps
+    /// running, once --threads of them [default: one per core] run further requests get 503
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8024")]
+        addr: String,
+    },
+    /// Read requests like {"day": 14, "input": "...", "part": 1} from stdin, one json object
+    /// per line, and answer each with a line like the ones of serve.
+    /// "variant" and "params", e.g. {"width": 11}, are optional. Like serve, every request is
+    /// solved once unless --iterations, --target-time or --bench ask for more
+    Batch,
+    /// Print a random input of the day selected with --day, the same seed gives the same input
+    Generate {
+        /// About the number of lines, entries or the side of the grid, depending on the day
+        #[arg(long, default_value = "50")]
+        size: usize,
+
+        #[arg(long, default_value = "0")]
+        seed: u64,
+    },
+    /// Time the selected days on generated inputs of growing size, print how fast the time
+    /// of every phase grows and draw it as a log-log chart per day.
+    /// A day stops growing at its first failure, e.g. when --budget runs out
+    Scale {
+        /// Sizes of the generated inputs, as for generate
+        #[arg(long, value_delimiter = ',', default_value = "8,16,32,64,128")]
+        sizes: Vec<usize>,
+
+        #[arg(long, default_value = "0")]
+        seed: u64,
+
+        /// Directory the charts are written to
+        #[arg(short, long, default_value = "plots")]
+        out: PathBuf,
+
+        #[arg(short, long, value_enum, default_value = "svg")]
+        format: plot::Format,
+    },
 }
 
-fn timed<F, R>(f: F, path: &str, label: &str, n: usize)
-where
-    F: Fn(&str) -> R,
-    R: Debug,
-{
-    let input = fs::read_to_string(path).unwrap();
-    let start = Instant::now();
-    for _ in 1..n {
-        f(&input);
+impl Args {
+    // options of the command line win over the config file
+    fn apply(&mut self, config: Config, matches: &ArgMatches) {
+        let given = |id| matches.value_source(id) == Some(ValueSource::CommandLine);
+        if let Some(input_base) = config.input_base.filter(|_| !given("input_base")) {
+            self.input_base = input_base;
+        }
+        if let Some(format) = config.format.filter(|_| !given("format")) {
+            self.format = format;
+        }
+        self.config_iterations = config.iterations;
+        // generate prints a single day, which needs to be given with --day
+        let own_day = matches!(self.command, Some(Command::Generate { .. }));
+        if self.days.is_empty() && !self.all && !own_day {
+            self.days = config.days;
+        }
+        // later budgets win
+        self.budget = config
+            .budget
+            .into_iter()
+            .chain(self.budget.drain(..))
+            .collect();
+        self.day_params = config.params;
+    }
+
+    // files in the input directory have a _test variant for the example inputs
+    fn input_file(&self, stem: &str, extension: &str) -> PathBuf {
+        let suffix = if self.test { "_test" } else { "" };
+        Path::new(&self.input_base).join(format!("{}{}.{}", stem, suffix, extension))
     }
-    let res = f(&input);
-    let end = Instant::now();
-    println!("{}: {:?}", label, res);
-    println!("Average: {:.2?}", (end - start) / n as u32);
+
+    fn budget(&self, day: u8) -> Option<Duration> {
+        runner::budget_for(&self.budget, day)
+    }
+
+    // outside the input directory, so that runs on the examples leave the tree clean.
+    // Entries are keyed by a hash of the input, so one file serves all input directories
+    fn cache_path(&self) -> PathBuf {
+        Path::new("target").join("answer_cache.json")
+    }
+
+    // only the answers are cached, benchmarks need to run
+    fn use_cache(&self) -> bool {
+        self.cache && !self.no_cache && !self.bench
+    }
+
+    fn parts(&self) -> Vec<usize> {
+        match self.part {
+            Some(part) => vec![part.into()],
+            None => vec![1, 2],
+        }
+    }
+
+    fn bench_config(&self) -> BenchConfig {
+        let default_iterations = match (self.bench, self.test) {
+            (true, _) => 30,
+            (false, true) => 1,
+            (false, false) => 5,
+        };
+        BenchConfig {
+            warmup: self.warmup.unwrap_or(if self.bench { 3 } else { 0 }),
+            until: match self.target_time {
+                Some(d) => Until::WallTime(d),
+                None => Until::Iterations(self.iterations().unwrap_or(default_iterations)),
+            },
+        }
+    }
+
+    // solutions that run out of their budget keep a thread busy, at most one per core
+    fn workers(&self) -> runner::Workers {
+        let cores = thread::available_parallelism().map_or(1, |n| n.get());
+        runner::Workers::new(self.threads.unwrap_or(cores))
+    }
+
+    fn iterations(&self) -> Option<usize> {
+        self.iterations.or(self.config_iterations)
+    }
+
+    // a request is answered, not benchmarked, unless the command line asks for it
+    fn request_config(&self) -> BenchConfig {
+        if self.bench || self.iterations.is_some() || self.target_time.is_some() {
+            return self.bench_config();
+        }
+        BenchConfig {
+            warmup: self.warmup.unwrap_or(0),
+            until: Until::Iterations(1),
+        }
+    }
+}
+
+// one implementation of a day that was run, or tried to
+struct Ran {
+    variant: &'static Variant,
+    result: Result<Solved, DayError>,
+    // answers from the cache have no timings
+    cached: bool,
 }
 
-fn call_timed(fn_and_label: (Solution, &str), base: &str, test: bool) {
-    timed(
-        fn_and_label.0,
-        &format!(
+type Input = (Arc<str>, Vec<(String, String)>);
+
+// base is the input directory
+fn input_path(day: &Day, base: &str, args: &Args) -> String {
+    args.input.clone().unwrap_or_else(|| {
+        format!(
             "{}/{}{}.txt",
             base,
-            fn_and_label.1,
-            if test { "_test" } else { "" }
+            day.label,
+            if args.test { "_test" } else { "" }
+        )
+    })
+}
+
+// the path and input of a day, with the parameters from the header if it is an example
+fn read_day(day: &Day, base: &str, args: &Args) -> (String, Result<Input, DayError>) {
+    let path = input_path(day, base, args);
+    // read once, stdin can't be read again for the next variant
+    let read = runner::read_input(&path).and_then(|text| {
+        if args.test {
+            let (header, input) = examples::split_header(&text)?;
+            Ok((Arc::<str>::from(input), header.params))
+        } else {
+            Ok((Arc::from(text), Vec::new()))
+        }
+    });
+    (path, read)
+}
+
+// the header of an example overrides the parameters of the config file,
+// the command line overrides both
+fn params_for(
+    day: &Day,
+    variant: &Variant,
+    header: &[(String, String)],
+    args: &Args,
+) -> Result<Params, DayError> {
+    let declared = variant.solution.params();
+    let configured = args.day_params.get(&day.day).into_iter().flatten();
+    let overrides = configured
+        // a variant may not have all parameters of its day
+        .filter(|(name, _)| declared.iter().any(|p| p.name == name))
+        .chain(header)
+        .chain(
+            args.params
+                .iter()
+                .filter(|(name, _)| declared.iter().any(|p| p.name == name)),
+        )
+        .cloned()
+        .collect_vec();
+    Ok(Params::resolve(declared, &overrides)?)
+}
+
+// reads the input of a day and solves it with every variant, returns the path of the input
+fn solve_day(
+    day: &Day,
+    base: &str,
+    variants: &[&'static Variant],
+    args: &Args,
+    config: &BenchConfig,
+    cache: &Mutex<Option<Cache>>,
+) -> (String, Vec<Ran>) {
+    let (path, read) = read_day(day, base, args);
+    let ran = match read {
+        Ok((input, header)) => variants
+            .iter()
+            .map(|v| {
+                let input = params_for(day, v, &header, args).map(|params| (input.clone(), params));
+                call_timed(day, v, input, args, config, cache)
+            })
+            .collect(),
+        Err(e) => vec![call_timed(day, variants[0], Err(e), args, config, cache)],
+    };
+    (path, ran)
+}
+
+fn call_timed(
+    day: &Day,
+    variant: &'static Variant,
+    input: Result<(Arc<str>, Params), DayError>,
+    args: &Args,
+    config: &BenchConfig,
+    cache: &Mutex<Option<Cache>>,
+) -> Ran {
+    let key = input
+        .as_ref()
+        .ok()
+        .map(|(input, params)| cache::key(&day.label_of(variant), params, input));
+    let cached = key.as_ref().and_then(|key| {
+        let cache = cache.lock().unwrap();
+        cache.as_ref()?.get(key, &args.parts())
+    });
+    let result = match cached.clone() {
+        Some(answers) => Ok(Solved {
+            answers,
+            timings: vec![],
+            memory: vec![],
+        }),
+        None => input.and_then(|(input, params)| match args.budget(day.day) {
+            Some(budget) => {
+                runner::solve_within(budget, variant.solution, input, params, *config, args.part)
+            }
+            None => runner::solve(variant.solution, &input, &params, config, args.part),
+        }),
+    };
+    if let (Some(key), Ok(solved), None) = (key, &result, &cached) {
+        if let Some(cache) = cache.lock().unwrap().as_mut() {
+            cache.set(key, &solved.answers);
+        }
+    }
+    // allocations of days running at the same time can't be told apart
+    let result = result.map(|solved| Solved {
+        memory: if args.parallel { vec![] } else { solved.memory },
+        ..solved
+    });
+    Ran {
+        variant,
+        result,
+        cached: cached.is_some(),
+    }
+}
+
+fn print_ran(day: &Day, path: &str, ran: &Ran, args: &Args) {
+    let record = || report::Record::new(day.day, ran.variant.name, path, &ran.result, ran.cached);
+    match args.format {
+        Format::Text => print_text(
+            &day.label_of(ran.variant),
+            &ran.result,
+            args.bench,
+            ran.cached,
         ),
-        fn_and_label.1,
-        if test { 1 } else { 5 },
+        Format::Json => println!("{}", record().json()),
+        Format::Csv => println!("{}", record().csv()),
+    }
+}
+
+// a row of the table --parallel prints at the end
+fn summary_row(label: String, ran: &Ran) -> [String; 5] {
+    let show = |answer: &Option<Answer>| answer.as_ref().map_or("-".into(), Answer::to_string);
+    match &ran.result {
+        Ok(Solved {
+            answers, timings, ..
+        }) => {
+            let total: Duration = timings.iter().map(|(_, stats)| stats.mean).sum();
+            let (time, status) = if ran.cached {
+                ("-".to_owned(), "cached")
+            } else {
+                (format!("{:.2?}", total), "ok")
+            };
+            [
+                label,
+                show(&answers.0),
+                show(&answers.1),
+                time,
+                status.to_owned(),
+            ]
+        }
+        Err(DayError::Timeout(elapsed)) => [
+            label,
+            "-".into(),
+            "-".into(),
+            format!("{:.2?}", elapsed),
+            "TIMEOUT".into(),
+        ],
+        Err(e) => [
+            label,
+            "-".into(),
+            "-".into(),
+            "-".into(),
+            format!("FAILED: {}", e),
+        ],
+    }
+}
+
+fn print_summary(rows: &[[String; 5]], elapsed: Duration) {
+    println!(
+        "{:<16} {:<20} {:<20} {:<10} status",
+        "day", "part1", "part2", "time"
     );
+    for [label, part1, part2, time, status] in rows {
+        println!(
+            "{:<16} {:<20} {:<20} {:<10} {}",
+            label, part1, part2, time, status
+        );
+    }
+    println!("Ran {} solutions in {:.2?}", rows.len(), elapsed);
 }
 
-type Solution = fn(&str) -> (usize, usize);
-
-fn main() {
-    let args = Args::parse();
-
-    let fn_and_labels: Vec<(Solution, &str)> = vec![
-        (day01::solve, "day01"),
-        (day02::solve, "day02"),
-        (day03::solve, "day03"),
-        (day04::solve, "day04"),
-        (day05::solve, "day05"),
-        (day06::solve, "day06"),
-        (day07::solve, "day07"),
-        (day08::solve, "day08"),
-        (day09::solve, "day09"),
-        (day10::solve, "day10"),
-        (day11::solve, "day11"),
-        (day12::solve, "day12"),
-        (day13::solve, "day13"),
-        (day14::solve, "day14"),
-        (day15::solve, "day15"),
-        (day16::solve, "day16"),
-        (day17::solve, "day17"),
-    ];
-
-    // underflow is fine
-    let (index, _) = args.day.overflowing_sub(1);
-    if let Some(&fn_and_label) = fn_and_labels.get(index as usize) {
-        call_timed(fn_and_label, &args.input_base, args.test);
+// "(41, 6)", or "(41, -)" if part 2 was not asked for
+fn show(answers: &(Option<Answer>, Option<Answer>)) -> String {
+    let show = |answer: &Option<Answer>| answer.as_ref().map_or("-".into(), Answer::to_string);
+    format!("({}, {})", show(&answers.0), show(&answers.1))
+}
+
+fn print_text(label: &str, result: &Result<Solved, DayError>, bench: bool, cached: bool) {
+    match result {
+        Ok(Solved { answers, .. }) if cached => {
+            println!("{}: {} (cached)", label, show(answers));
+        }
+        Ok(Solved {
+            answers,
+            timings,
+            memory,
+        }) => {
+            println!("{}: {}", label, show(answers));
+            if bench {
+                for (phase, stats) in timings {
+                    println!("{}: {}", phase, stats);
+                }
+            } else {
+                let total: Duration = timings.iter().map(|(_, stats)| stats.mean).sum();
+                let phases = timings
+                    .iter()
+                    .map(|(phase, stats)| format!("{} {:.2?}", phase, stats.mean))
+                    .join(", ");
+                println!("Average: {:.2?} ({})", total, phases);
+            }
+            for (phase, memory) in memory {
+                println!("{} memory: {}", phase, memory);
+            }
+        }
+        Err(DayError::Timeout(elapsed)) => println!("{}: TIMEOUT after {:.2?}", label, elapsed),
+        Err(e) => println!("{}: FAILED: {}", label, e),
+    }
+}
+
+// prints a table comparing against the expected answers, returns whether all answers match.
+// An answer that is not known counts as a failure unless allow_unknown, so that a check
+// without answers to check against doesn't pass
+fn check(
+    expected: &Answers,
+    results: &[(&str, Option<Solved>)],
+    parts: &[usize],
+    allow_unknown: bool,
+    format: Format,
+) -> bool {
+    let mut all_ok = true;
+    say!(
+        format,
+        "{:<6} {:<4} {:<20} {:<20} status",
+        "day",
+        "part",
+        "expected",
+        "actual"
+    );
+    for (label, solved) in results {
+        for &part in parts {
+            let actual = solved.as_ref().and_then(|Solved { answers, .. }| {
+                if part == 1 { &answers.0 } else { &answers.1 }
+                    .as_ref()
+                    .map(Answer::to_string)
+            });
+            let expected = expected.get(label, part);
+            let status = match (expected, &actual) {
+                (_, None) => "FAILED",
+                (None, Some(_)) => "unknown",
+                (Some(e), Some(a)) if e == a => "ok",
+                (Some(_), Some(_)) => "MISMATCH",
+            };
+            all_ok &= status == "ok" || (status == "unknown" && allow_unknown);
+            say!(
+                format,
+                "{:<6} {:<4} {:<20} {:<20} {}",
+                label,
+                part,
+                expected.unwrap_or("-"),
+                actual.as_deref().unwrap_or("-"),
+                status
+            );
+        }
+    }
+    all_ok
+}
+
+// prints whether all variants of a day came to the same answers
+fn cross_check(day: &Day, solved: &[(&Variant, Option<Solved>)], format: Format) -> bool {
+    let answers: Vec<_> = solved
+        .iter()
+        .map(|(variant, solved)| (variant.name, solved.as_ref().map(|s| &s.answers)))
+        .collect();
+    // compared as printed, cached answers are text whatever type the solution returned
+    let agree = answers.iter().all(|(_, a)| a.is_some())
+        && answers.iter().map(|(_, a)| a.map(show)).all_equal();
+    if agree {
+        say!(format, "{}: {} variants agree", day.label, answers.len());
     } else {
-        println!("Solving all...");
-        fn_and_labels
+        let each = answers
             .iter()
-            .for_each(|&fn_and_label| call_timed(fn_and_label, &args.input_base, args.test));
+            .map(|(name, a)| format!("{} {}", name, a.map_or("FAILED".into(), show)))
+            .join(", ");
+        say!(format, "{}: VARIANTS DISAGREE: {}", day.label, each);
+    }
+    agree
+}
+
+fn plot(args: &Args, out: &Path, format: plot::Format) -> ExitCode {
+    let rendered = history::load(&args.input_file("bench_history", "csv"))
+        .map_err(|e| e.to_string())
+        .and_then(|history| plot::render(&history, out, format).map_err(|e| e.to_string()));
+    match rendered {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            println!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// --days restricts the examples to those days, returns failure if any example fails
+fn examples(args: &Args) -> ExitCode {
+    let paths = match examples::discover(Path::new(&args.input_base)) {
+        Ok(paths) => paths,
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let selected = |day| args.days.is_empty() || args.days.iter().any(|r| r.contains(&day));
+
+    println!(
+        "{:<24} {:<14} {:<4} {:<20} {:<20} status",
+        "example", "solution", "part", "expected", "actual"
+    );
+    let (mut count, mut failed) = (0, Vec::new());
+    for path in paths {
+        let example = Example::load(&path);
+        if example.as_ref().is_ok_and(|e| !selected(e.day)) {
+            continue;
+        }
+        count += 1;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let runs = match example.and_then(|e| e.run()) {
+            Ok(runs) => runs,
+            Err(e) => vec![("-".to_owned(), Err(e))],
+        };
+        let mut ok = true;
+        for (label, verdicts) in runs {
+            let verdicts = match verdicts {
+                Ok(verdicts) => verdicts,
+                Err(e) => {
+                    ok = false;
+                    println!("{:<24} {:<14} FAILED: {}", name, label, e);
+                    continue;
+                }
+            };
+            for (part, verdict) in (1..).zip(verdicts) {
+                let (expected, actual, status) = match verdict {
+                    Verdict::Pass(answer) => (answer.clone(), answer, "ok"),
+                    Verdict::Fail { expected, actual } => (expected, actual, "MISMATCH"),
+                    Verdict::Unchecked(actual) => ("-".to_owned(), actual, "unknown"),
+                };
+                ok &= status != "MISMATCH";
+                println!(
+                    "{:<24} {:<14} {:<4} {:<20} {:<20} {}",
+                    name, label, part, expected, actual, status
+                );
+            }
+        }
+        if !ok {
+            failed.push(name.into_owned());
+        }
+    }
+
+    println!("{} of {} examples passed", count - failed.len(), count);
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        println!("Failed: {}", failed.join(", "));
+        ExitCode::FAILURE
+    }
+}
+
+// answers requests until interrupted
+fn serve(args: &Args, addr: &str) -> ExitCode {
+    let server = match tiny_http::Server::http(addr) {
+        Ok(server) => server,
+        Err(e) => {
+            println!("Cannot listen on {}: {}", addr, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Listening on http://{}, Ctrl-C to stop", addr);
+    let service = serve::Service {
+        config: args.request_config(),
+        budgets: args.budget.clone(),
+        workers: args.workers(),
+    };
+    service.run(&server);
+    ExitCode::SUCCESS
+}
+
+fn generate(args: &Args, size: usize, seed: u64) -> ExitCode {
+    let days = args.days.iter().cloned().flatten().collect_vec();
+    let [day] = days[..] else {
+        println!("generate needs a single day, select it with --day");
+        return ExitCode::FAILURE;
+    };
+    match generate::input(day, size, seed) {
+        Ok(Some(input)) => {
+            print!("{}", input);
+            ExitCode::SUCCESS
+        }
+        Ok(None) => {
+            println!("day{:02} has no generator", day);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            println!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// answers requests from stdin until it is closed
+fn batch(args: &Args) -> ExitCode {
+    let service = serve::Service {
+        config: args.request_config(),
+        budgets: args.budget.clone(),
+        workers: args.workers(),
+    };
+    match batch::run(&service, io::stdin().lock(), io::stdout().lock()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn load_cache(args: &Args) -> Mutex<Option<Cache>> {
+    let cache = args.use_cache().then(|| {
+        Cache::load(&args.cache_path()).unwrap_or_else(|e| {
+            // the answers can still be computed, the broken file is replaced
+            say!(args.format, "{}, starting a new one", e);
+            Cache::new()
+        })
+    });
+    Mutex::new(cache)
+}
+
+fn save_cache(args: &Args, cache: Mutex<Option<Cache>>) {
+    if let Some(cache) = cache.into_inner().unwrap() {
+        if let Err(e) = cache.save(&args.cache_path()) {
+            say!(args.format, "{}", e);
+        }
+    }
+}
+
+// solves and prints the selected days, then checks and records their answers
+fn solve_days(
+    args: &Args,
+    selected: &[(&'static Day, Vec<&'static Variant>)],
+    all: bool,
+) -> ExitCode {
+    let cache = load_cache(args);
+    if all {
+        say!(args.format, "Solving all...");
+    }
+    if args.format == Format::Csv {
+        println!("{}", report::Record::csv_header());
+    }
+    let config = args.bench_config();
+    let started = Instant::now();
+    let solve = |(day, variants): &(&'static Day, Vec<&'static Variant>)| {
+        let base = &args.input_base;
+        (*day, solve_day(day, base, variants, args, &config, &cache))
+    };
+    // sequential days are printed as soon as they are done, parallel ones when all are.
+    // A thread per day rather than the rayon pool: a day waiting for its budget blocks its
+    // thread, and rayon workers blocked that way would starve the solutions using rayon.
+    let solved_days: Box<dyn Iterator<Item = _>> = if args.parallel {
+        let solved: Vec<_> = thread::scope(|scope| {
+            let handles: Vec<_> = selected
+                .iter()
+                .map(|day| scope.spawn(|| solve(day)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|h| h.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+                .collect()
+        });
+        Box::new(solved.into_iter())
+    } else {
+        Box::new(selected.iter().map(solve))
+    };
+
+    let timestamp = history::now();
+    let mut records = Vec::new();
+    let mut disagree = Vec::new();
+    let mut results = Vec::new();
+    let mut summary = Vec::new();
+    for (day, (path, ran)) in solved_days {
+        for ran in &ran {
+            if args.parallel && args.format == Format::Text {
+                summary.push(summary_row(day.label_of(ran.variant), ran));
+            } else {
+                print_ran(day, &path, ran, args);
+            }
+        }
+        let solved: Vec<_> = ran
+            .into_iter()
+            .map(|r| (r.variant, r.result.ok()))
+            .collect();
+        if solved.len() > 1 && !cross_check(day, &solved, args.format) {
+            disagree.push(day.label);
+        }
+        for (variant, solved) in &solved {
+            let timings = solved.iter().flat_map(|s| &s.timings);
+            records.extend(timings.map(|&(phase, stats)| Record {
+                timestamp,
+                label: day.label_of(variant),
+                phase,
+                stats,
+            }));
+        }
+        // the answers of the first variant stand for the day
+        results.push((day.label, solved.into_iter().next().and_then(|(_, s)| s)));
+    }
+
+    if args.bench {
+        let history_path = args.input_file("bench_history", "csv");
+        if let Err(e) = history::append(&history_path, &records) {
+            say!(args.format, "{}", e);
+        }
+    }
+
+    if !summary.is_empty() {
+        print_summary(&summary, started.elapsed());
+    }
+
+    save_cache(args, cache);
+
+    let failed: Vec<_> = results
+        .iter()
+        .filter(|(_, solved)| solved.is_none())
+        .map(|(label, _)| *label)
+        .collect();
+    if results.len() > 1 && !failed.is_empty() {
+        say!(args.format, "Failed: {}", failed.join(", "));
+    }
+
+    if results.len() > 1 && !disagree.is_empty() {
+        say!(args.format, "Variants disagree: {}", disagree.join(", "));
+    }
+
+    let answers_path = args.input_file("answers", "toml");
+    let mut ok = failed.is_empty() && disagree.is_empty();
+    if args.check || args.record {
+        let mut expected = match Answers::load(&answers_path) {
+            Ok(answers) => answers,
+            Err(e) => {
+                say!(args.format, "{}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        if args.check {
+            ok &= check(
+                &expected,
+                &results,
+                &args.parts(),
+                args.allow_unknown,
+                args.format,
+            );
+        }
+        if args.record {
+            for (label, solved) in &results {
+                if let Some(Solved { answers, .. }) = solved {
+                    for (part, answer) in [(1, &answers.0), (2, &answers.1)] {
+                        if let Some(answer) = answer {
+                            expected.set(label, part, answer);
+                        }
+                    }
+                }
+            }
+            if let Err(e) = expected.save(&answers_path) {
+                say!(args.format, "{}", e);
+                return ExitCode::FAILURE;
+            }
+            say!(
+                args.format,
+                "Recorded answers in {}",
+                answers_path.display()
+            );
+        }
+    }
+
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn main() -> ExitCode {
+    let matches = Args::command().get_matches();
+    let mut args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    match Config::load(args.config.as_deref()) {
+        Ok(config) => args.apply(config, &matches),
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    match &args.command {
+        Some(Command::Plot { out, format }) => return plot(&args, out, *format),
+        Some(Command::Examples) => return examples(&args),
+        Some(Command::Serve { addr }) => return serve(&args, addr),
+        Some(Command::Batch) => return batch(&args),
+        Some(Command::Generate { size, seed }) => return generate(&args, *size, *seed),
+        // needs the selected days and variants
+        Some(Command::Scale { .. }) | None => {}
+    }
+
+    if let Some(threads) = args.threads {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads);
+        if let Err(e) = pool.build_global() {
+            say!(args.format, "Cannot start {} threads: {}", threads, e);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    let all = args.all || args.days.is_empty();
+    let days: Vec<&Day> = if all {
+        registry::DAYS.iter().collect()
+    } else {
+        let numbers = args.days.iter().cloned().flatten().sorted().dedup();
+        let (unknown, days): (Vec<_>, Vec<_>) =
+            numbers.partition_map(|n| registry::get(n).ok_or(n).into());
+        if !unknown.is_empty() {
+            say!(
+                args.format,
+                "Unknown day {}, there are solutions for days {}",
+                unknown.iter().join(", "),
+                registry::DAYS.iter().map(|d| d.day).join(", ")
+            );
+            return ExitCode::FAILURE;
+        }
+        days
+    };
+
+    if args.input.is_some() && days.len() != 1 {
+        say!(
+            args.format,
+            "--input needs a single day, select it with --days"
+        );
+        return ExitCode::FAILURE;
+    }
+
+    // the variants to run for every day
+    let mut selected = Vec::new();
+    for day in days {
+        let variants: Vec<&Variant> = if args.cross_check {
+            day.variants.iter().collect()
+        } else if let Some(variant) = day.variant(args.variant.as_deref()) {
+            vec![variant]
+        } else {
+            say!(
+                args.format,
+                "{} has no variant {}, there are {}",
+                day.label,
+                args.variant.as_deref().unwrap_or_default(),
+                day.variants.iter().map(|v| v.name).join(", ")
+            );
+            return ExitCode::FAILURE;
+        };
+        selected.push((day, variants));
+    }
+
+    // every --param needs to be known to at least one of the selected implementations
+    let declared = selected
+        .iter()
+        .flat_map(|(_, variants)| variants.iter().flat_map(|v| v.solution.params()))
+        .unique_by(|p| p.name)
+        .collect_vec();
+    if let Some((name, _)) = args
+        .params
+        .iter()
+        .find(|(name, _)| !declared.iter().any(|p| p.name == name))
+    {
+        say!(args.format, "Unknown parameter {}", name);
+        if declared.is_empty() {
+            say!(args.format, "The selected days have no parameters");
+        } else {
+            say!(args.format, "The selected days have:");
+            for param in declared {
+                say!(
+                    args.format,
+                    "  {:<10} {} ({}, default {})",
+                    param.name,
+                    param.help,
+                    param.kind,
+                    param.default
+                );
+            }
+        }
+        return ExitCode::FAILURE;
+    }
+    if args.clear_cache {
+        if let Err(e) = Cache::clear(&args.cache_path()) {
+            say!(args.format, "{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    if let Some(Command::Scale {
+        sizes,
+        seed,
+        out,
+        format,
+    }) = &args.command
+    {
+        return scale::run(&args, &selected, sizes, *seed, out, *format);
+    }
+    if args.determinism {
+        return determinism::run(&selected, &args);
+    }
+    if args.watch {
+        return watch::run(&args, &selected, all);
+    }
+    if !args.inputs.is_empty() {
+        return matrix::run(&args, &selected);
+    }
+    solve_days(&args, &selected, all)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(command_line: &[&str], config: &str) -> Args {
+        let matches = Args::command().get_matches_from(command_line);
+        let mut args = Args::from_arg_matches(&matches).unwrap();
+        args.apply(config.parse().unwrap(), &matches);
+        args
+    }
+
+    #[test]
+    fn test_config_iterations() {
+        let once = Until::Iterations(1);
+        let args = parsed(&["aoc24", "batch"], "iterations = 3");
+        assert_eq!(args.request_config().until, once);
+        assert_eq!(args.bench_config().until, Until::Iterations(3));
+        let args = parsed(&["aoc24", "--bench", "serve"], "iterations = 3");
+        assert_eq!(args.request_config().until, Until::Iterations(3));
+        let args = parsed(&["aoc24", "--iterations", "2", "batch"], "iterations = 3");
+        assert_eq!(args.request_config().until, Until::Iterations(2));
+        let args = parsed(&["aoc24", "--iterations", "2", "batch"], "");
+        assert_eq!(args.request_config().until, Until::Iterations(2));
+    }
+
+    #[test]
+    fn test_check() {
+        let expected: Answers = "[day01]\npart1 = 11\n".parse().unwrap();
+        let solved = |part1: usize| Solved {
+            answers: (Some(Answer::from(part1)), Some(Answer::from(31usize))),
+            timings: vec![],
+            memory: vec![],
+        };
+        let check = |results: &[(&str, Option<Solved>)], parts: &[usize], allow_unknown| {
+            check(&expected, results, parts, allow_unknown, Format::Json)
+        };
+        assert!(check(&[("day01", Some(solved(11)))], &[1], false));
+        assert!(!check(&[("day01", Some(solved(12)))], &[1], true));
+        assert!(!check(&[("day01", None)], &[1], true));
+        // part 2 and day02 are not known
+        assert!(!check(&[("day01", Some(solved(11)))], &[1, 2], false));
+        assert!(check(&[("day01", Some(solved(11)))], &[1, 2], true));
+        assert!(!check(&[("day02", Some(solved(11)))], &[1], false));
     }
 }
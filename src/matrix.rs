@@ -0,0 +1,334 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use itertools::Itertools;
+use rayon::prelude::*;
+
+use aoc24::{
+    registry::{Day, Variant},
+    runner::Solved,
+    solution::{Answer, DayError},
+};
+
+use crate::{
+    input_path, load_cache, print_ran,
+    report::{self, Format},
+    save_cache, solve_day, Args, Ran,
+};
+
+// The answers and timings of the selected days for several input directories,
+// e.g. the inputs of different accounts, to find inputs that are unusually slow.
+
+// how one variant did on one input
+pub enum Cell {
+    // the directory has no input for the day
+    Missing,
+    Failed(String),
+    Solved {
+        answers: [String; 2],
+        // None for cached answers
+        time: Option<Duration>,
+    },
+}
+
+// one variant of a day against all inputs, in the order of the inputs
+pub struct Row {
+    pub label: String,
+    pub cells: Vec<Cell>,
+}
+
+// "inputs/*" is every directory in inputs, * and ? only match within one component
+// and like in a shell they don't match a leading dot
+pub fn expand(pattern: &str) -> Vec<PathBuf> {
+    let mut matches = vec![PathBuf::new()];
+    for component in Path::new(pattern).components() {
+        let part = component.as_os_str().to_string_lossy();
+        if !part.contains(['*', '?']) {
+            for path in &mut matches {
+                path.push(component);
+            }
+            continue;
+        }
+        matches = matches
+            .iter()
+            .flat_map(|dir| {
+                let listed = if dir.as_os_str().is_empty() {
+                    fs::read_dir(".")
+                } else {
+                    fs::read_dir(dir)
+                };
+                listed
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                    .filter(|name| {
+                        (part.starts_with('.') || !name.starts_with('.')) && wildcard(&part, name)
+                    })
+                    .map(|name| dir.join(name))
+                    .collect_vec()
+            })
+            .collect();
+    }
+    let mut dirs = matches.into_iter().filter(|p| p.is_dir()).collect_vec();
+    dirs.sort();
+    dirs
+}
+
+// * is any number of characters, ? exactly one
+fn wildcard(pattern: &str, name: &str) -> bool {
+    let mut rest = pattern.chars();
+    match rest.next() {
+        None => name.is_empty(),
+        Some('*') => {
+            wildcard(rest.as_str(), name)
+                || name
+                    .chars()
+                    .next()
+                    .is_some_and(|c| wildcard(pattern, &name[c.len_utf8()..]))
+        }
+        Some(p) => {
+            let mut name = name.chars();
+            match name.next() {
+                Some(c) if p == '?' || p == c => wildcard(rest.as_str(), name.as_str()),
+                _ => false,
+            }
+        }
+    }
+}
+
+// (row, input, how many times slower than the median) of every cell that takes more than
+// factor times the median of its row, rows need at least two timed cells
+pub fn outliers(rows: &[Row], factor: f64) -> Vec<(usize, usize, f64)> {
+    let mut slow = Vec::new();
+    for (r, row) in rows.iter().enumerate() {
+        let times = row
+            .cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| match cell {
+                Cell::Solved {
+                    time: Some(time), ..
+                } => Some((i, *time)),
+                _ => None,
+            })
+            .collect_vec();
+        if times.len() < 2 {
+            continue;
+        }
+        // the lower median, so that one slow input out of two is still noticed
+        let sorted = times.iter().map(|(_, t)| *t).sorted().collect_vec();
+        let median = sorted[(sorted.len() - 1) / 2].as_secs_f64();
+        if median == 0.0 {
+            continue;
+        }
+        for (i, time) in times {
+            let ratio = time.as_secs_f64() / median;
+            if ratio > factor {
+                slow.push((r, i, ratio));
+            }
+        }
+    }
+    slow
+}
+
+// a block per row with a line per input
+pub fn print(inputs: &[String], rows: &[Row], factor: f64) {
+    let slow = outliers(rows, factor);
+    let width = inputs.iter().map(|i| i.len()).max().unwrap_or(0).max(14) + 2;
+    for (r, row) in rows.iter().enumerate() {
+        println!(
+            "{:<width$} {:<20} {:<20} {:<10} status",
+            row.label, "part1", "part2", "time"
+        );
+        for (i, (input, cell)) in inputs.iter().zip(&row.cells).enumerate() {
+            let (part1, part2, time, status) = match cell {
+                Cell::Missing => ("-", "-", "-".to_owned(), "missing".to_owned()),
+                Cell::Failed(e) => ("-", "-", "-".to_owned(), e.clone()),
+                Cell::Solved { answers, time } => {
+                    let status = match slow.iter().find(|&&(sr, si, _)| (sr, si) == (r, i)) {
+                        Some((_, _, ratio)) => format!("SLOW {:.1}x", ratio),
+                        None if time.is_none() => "cached".to_owned(),
+                        None => "ok".to_owned(),
+                    };
+                    let time = time.map_or("-".to_owned(), |t| format!("{:.2?}", t));
+                    (answers[0].as_str(), answers[1].as_str(), time, status)
+                }
+            };
+            println!(
+                "  {:<w$} {:<20} {:<20} {:<10} {}",
+                input,
+                part1,
+                part2,
+                time,
+                status,
+                w = width - 2
+            );
+        }
+    }
+}
+
+// the answers of a variant for one input of the matrix
+fn cell(ran: &Ran) -> Cell {
+    let show = |answer: &Option<Answer>| answer.as_ref().map_or("-".into(), Answer::to_string);
+    match &ran.result {
+        Ok(Solved {
+            answers, timings, ..
+        }) => Cell::Solved {
+            answers: [show(&answers.0), show(&answers.1)],
+            time: (!ran.cached).then(|| timings.iter().map(|(_, stats)| stats.mean).sum()),
+        },
+        Err(DayError::Timeout(_)) => Cell::Failed("TIMEOUT".to_owned()),
+        Err(e) => Cell::Failed(format!("FAILED: {}", e)),
+    }
+}
+
+// solves every selected day against every directory of --inputs, fails if any of them fails
+pub fn run(args: &Args, selected: &[(&'static Day, Vec<&'static Variant>)]) -> ExitCode {
+    let mut dirs = Vec::new();
+    for pattern in &args.inputs {
+        let found = expand(pattern);
+        if found.is_empty() {
+            say!(args.format, "{} matches no input directory", pattern);
+            return ExitCode::FAILURE;
+        }
+        dirs.extend(found);
+    }
+    let dirs = dirs
+        .into_iter()
+        .map(|dir| dir.display().to_string())
+        .sorted()
+        .dedup()
+        .collect_vec();
+
+    let cache = load_cache(args);
+    if args.format == Format::Csv {
+        println!("{}", report::Record::csv_header());
+    }
+    let config = args.bench_config();
+    let started = Instant::now();
+    let jobs = selected
+        .iter()
+        .flat_map(|(day, variants)| dirs.iter().map(move |dir| (*day, variants, dir)))
+        .collect_vec();
+    // a day without an input in one of the directories is left out of that column
+    let solve = |&(day, variants, dir): &(&'static Day, &Vec<&'static Variant>, &String)| {
+        let path = input_path(day, dir, args);
+        Path::new(&path)
+            .exists()
+            .then(|| solve_day(day, dir, variants, args, &config, &cache))
+    };
+    let solved: Vec<Option<(String, Vec<Ran>)>> = if args.parallel {
+        jobs.par_iter().map(solve).collect()
+    } else {
+        jobs.iter().map(solve).collect()
+    };
+    save_cache(args, cache);
+
+    let mut rows = Vec::new();
+    for ((day, variants), solved) in selected.iter().zip(solved.chunks(dirs.len())) {
+        for (v, variant) in variants.iter().enumerate() {
+            let cells = solved
+                .iter()
+                .map(|solved| match solved {
+                    // a failed read has a single entry for all variants
+                    Some((_, ran)) => cell(&ran[v.min(ran.len() - 1)]),
+                    None => Cell::Missing,
+                })
+                .collect();
+            rows.push(Row {
+                label: day.label_of(variant),
+                cells,
+            });
+        }
+        if args.format != Format::Text {
+            for (path, ran) in solved.iter().flatten() {
+                for ran in ran {
+                    print_ran(day, path, ran, args);
+                }
+            }
+        }
+    }
+    if args.format == Format::Text {
+        print(&dirs, &rows, args.outlier_factor);
+    }
+
+    say!(
+        args.format,
+        "Solved {} days against {} inputs in {:.2?}",
+        selected.len(),
+        dirs.len(),
+        started.elapsed()
+    );
+    let slow = outliers(&rows, args.outlier_factor);
+    if !slow.is_empty() {
+        let slow = slow
+            .iter()
+            .map(|&(r, i, ratio)| format!("{} with {} ({:.1}x)", rows[r].label, dirs[i], ratio))
+            .join(", ");
+        say!(args.format, "Slow: {}", slow);
+    }
+    let failed = rows
+        .iter()
+        .flat_map(|row| {
+            row.cells
+                .iter()
+                .zip(&dirs)
+                .filter(|(cell, _)| matches!(cell, Cell::Failed(_)))
+                .map(|(_, dir)| format!("{} with {}", row.label, dir))
+        })
+        .collect_vec();
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        say!(args.format, "Failed: {}", failed.join(", "));
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard() {
+        assert!(wildcard("input*", "input_alice"));
+        assert!(wildcard("input*", "input"));
+        assert!(wildcard("*_?", "input_b"));
+        assert!(wildcard("*", "äöü"));
+        assert!(!wildcard("*_?", "input_bob"));
+        assert!(!wildcard("input", "inputs"));
+    }
+
+    #[test]
+    fn test_outliers() {
+        let solved = |ms| Cell::Solved {
+            answers: ["1".into(), "2".into()],
+            time: Some(Duration::from_millis(ms)),
+        };
+        let rows = [
+            Row {
+                label: "day06".into(),
+                cells: vec![solved(10), Cell::Missing, solved(12), solved(50)],
+            },
+            Row {
+                label: "day07".into(),
+                cells: vec![solved(1), Cell::Failed("TIMEOUT".into()), solved(100)],
+            },
+            // a single timed cell has nothing to compare with
+            Row {
+                label: "day08".into(),
+                cells: vec![solved(100)],
+            },
+        ];
+        let slow = outliers(&rows, 3.0);
+        assert_eq!(slow.len(), 2);
+        assert_eq!((slow[0].0, slow[0].1), (0, 3));
+        assert!((slow[0].2 - 50.0 / 12.0).abs() < 1e-9);
+        assert_eq!((slow[1].0, slow[1].1), (1, 2));
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::BTreeMap;
+
+use itertools::Itertools;
+use serde::Deserialize;
+use thiserror::Error;
+
+/// A puzzle constant that can be changed from the command line, e.g. the size of the room
+/// on day 14, which is different for the example
+#[derive(Debug)]
+pub struct Param {
+    pub name: &'static str,
+    pub kind: ParamKind,
+    pub default: &'static str,
+    pub help: &'static str,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ParamKind {
+    /// Integer between min and max, both included
+    Unsigned { min: u64, max: u64 },
+    /// Comma separated integers between 0 and max
+    List { max: u64 },
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParamError {
+    #[error("Unknown parameter {name}, known are: {known}")]
+    Unknown { name: String, known: String },
+    #[error("Bad value for {name}: {value}, expected {expected}")]
+    Invalid {
+        name: &'static str,
+        value: String,
+        expected: String,
+    },
+    #[error("Parameters are given as key=value, not {0}")]
+    WrongFormat(String),
+}
+
+/// Values of all declared parameters of a day, validated against their kind
+#[derive(Debug, Clone, Default)]
+pub struct Params(BTreeMap<&'static str, String>);
+
+impl Params {
+    /// The defaults of declared, with overrides applied. Every override needs to be declared.
+    pub fn resolve(
+        declared: &'static [Param],
+        overrides: &[(String, String)],
+    ) -> Result<Params, ParamError> {
+        let mut values: BTreeMap<_, _> = declared
+            .iter()
+            .map(|p| (p.name, p.default.to_owned()))
+            .collect();
+        for (name, value) in overrides {
+            let Some(param) = declared.iter().find(|p| p.name == name) else {
+                return Err(ParamError::Unknown {
+                    name: name.clone(),
+                    known: declared.iter().map(|p| p.name).join(", "),
+                });
+            };
+            values.insert(param.name, value.clone());
+        }
+        for param in declared {
+            param.kind.validate(param.name, &values[param.name])?;
+        }
+        Ok(Params(values))
+    }
+
+    pub fn defaults(declared: &'static [Param]) -> Params {
+        Params::resolve(declared, &[]).expect("Defaults need to be valid")
+    }
+
+    /// Panics if name was not declared as `Unsigned`
+    pub fn unsigned(&self, name: &str) -> u64 {
+        self.value(name).parse().expect("Validated when resolved")
+    }
+
+    /// Panics if name was not declared as `List`
+    pub fn list(&self, name: &str) -> Vec<u64> {
+        parse_list(self.value(name)).expect("Validated when resolved")
+    }
+
+    fn value(&self, name: &str) -> &str {
+        self.0
+            .get(name)
+            .unwrap_or_else(|| panic!("Parameter {} was not declared", name))
+    }
+}
+
+// "height=7 width=11", empty without parameters
+impl std::fmt::Display for Params {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value));
+        write!(f, "{}", values.format(" "))
+    }
+}
+
+impl ParamKind {
+    fn validate(self, name: &'static str, value: &str) -> Result<(), ParamError> {
+        let valid = match self {
+            ParamKind::Unsigned { min, max } => {
+                value.parse::<u64>().is_ok_and(|v| (min..=max).contains(&v))
+            }
+            ParamKind::List { max } => {
+                parse_list(value).is_some_and(|l| l.iter().all(|&v| v <= max))
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(ParamError::Invalid {
+                name,
+                value: value.to_owned(),
+                expected: self.to_string(),
+            })
+        }
+    }
+}
+
+impl std::fmt::Display for ParamKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamKind::Unsigned { min, max } => write!(f, "an integer from {} to {}", min, max),
+            ParamKind::List { max } => write!(f, "comma separated integers up to {}", max),
+        }
+    }
+}
+
+fn parse_list(value: &str) -> Option<Vec<u64>> {
+    value.split(',').map(|v| v.trim().parse().ok()).collect()
+}
+
+/// The value of a parameter in a config file or a json request. Numbers and lists of
+/// numbers are taken as they would be written on the command line, so `[2, 4, 1]` is "2,4,1"
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(untagged)]
+pub enum ParamValue {
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+    List(Vec<ParamValue>),
+}
+
+impl std::fmt::Display for ParamValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamValue::Unsigned(n) => write!(f, "{}", n),
+            ParamValue::Signed(n) => write!(f, "{}", n),
+            ParamValue::Float(n) => write!(f, "{}", n),
+            ParamValue::Bool(b) => write!(f, "{}", b),
+            ParamValue::Text(s) => write!(f, "{}", s),
+            ParamValue::List(values) => write!(f, "{}", values.iter().format(",")),
+        }
+    }
+}
+
+/// "key=value" from the command line
+pub fn parse_assignment(s: &str) -> Result<(String, String), ParamError> {
+    s.split_once('=')
+        .map(|(k, v)| (k.trim().to_owned(), v.trim().to_owned()))
+        .ok_or_else(|| ParamError::WrongFormat(s.to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static DECLARED: &[Param] = &[
+        Param {
+            name: "width",
+            kind: ParamKind::Unsigned { min: 1, max: 1000 },
+            default: "101",
+            help: "",
+        },
+        Param {
+            name: "program",
+            kind: ParamKind::List { max: 7 },
+            default: "2,4,1",
+            help: "",
+        },
+    ];
+
+    fn assign(s: &str) -> (String, String) {
+        parse_assignment(s).unwrap()
+    }
+
+    #[test]
+    fn test_defaults() {
+        let params = Params::defaults(DECLARED);
+        assert_eq!(params.unsigned("width"), 101);
+        assert_eq!(params.list("program"), vec![2, 4, 1]);
+    }
+
+    #[test]
+    fn test_overrides() {
+        let params = Params::resolve(DECLARED, &[assign("width=11"), assign("program=0,3")]);
+        assert_eq!(params.unwrap().unsigned("width"), 11);
+        assert!(matches!(
+            Params::resolve(DECLARED, &[assign("height=7")]),
+            Err(ParamError::Unknown { .. })
+        ));
+        for bad in [
+            "width=0",
+            "width=-1",
+            "width=x",
+            "program=8",
+            "program=1,,2",
+        ] {
+            assert!(matches!(
+                Params::resolve(DECLARED, &[assign(bad)]),
+                Err(ParamError::Invalid { .. })
+            ));
+        }
+        assert!(parse_assignment("width").is_err());
+    }
+
+    #[test]
+    fn test_param_value() {
+        let value = |json: &str| {
+            serde_json::from_str::<ParamValue>(json)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(value("11"), "11");
+        assert_eq!(value("-1"), "-1");
+        assert_eq!(value(r#""7""#), "7");
+        assert_eq!(value("[2, 4, 1]"), "2,4,1");
+        assert_eq!(value("true"), "true");
+    }
+}
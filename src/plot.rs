@@ -0,0 +1,336 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use clap::ValueEnum;
+use itertools::Itertools;
+use plotters::{coord::Shift, prelude::*};
+use thiserror::Error;
+
+use aoc24::solution::Phase;
+
+use crate::{history::Record, scale::Series};
+
+const SIZE: (u32, u32) = (1024, 640);
+const PHASES: [(Phase, RGBColor); 3] = [
+    (Phase::Parse, RGBColor(0x4c, 0x72, 0xb0)),
+    (Phase::Part1, RGBColor(0xdd, 0x84, 0x52)),
+    (Phase::Part2, RGBColor(0x55, 0xa8, 0x68)),
+];
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Svg,
+    Png,
+}
+
+impl Format {
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Svg => "svg",
+            Format::Png => "png",
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PlotError {
+    #[error("Cannot write chart: {0}")]
+    Io(#[from] io::Error),
+    #[error("Cannot draw chart: {0}")]
+    Draw(String),
+    #[error("No benchmark history to plot, run with --bench first")]
+    NoHistory,
+}
+
+impl<E: std::error::Error + Send + Sync> From<DrawingAreaErrorKind<E>> for PlotError {
+    fn from(e: DrawingAreaErrorKind<E>) -> Self {
+        PlotError::Draw(e.to_string())
+    }
+}
+
+enum Chart<'a> {
+    // median of every phase of one day over all runs
+    Trend(&'a str, Vec<&'a Record>),
+    // the last run of every day, phases stacked
+    // (both have at least one run, segmented ranges include their end)
+    Latest(Vec<(&'a str, Vec<&'a Record>)>),
+    // time over input size of every phase of the variants of one day, log-log
+    Scaling(&'a str, Vec<&'a Series>),
+}
+
+// Renders one trend chart per day and a bar chart of all days into out, returns the written files
+pub fn render(history: &[Record], out: &Path, format: Format) -> Result<Vec<PathBuf>, PlotError> {
+    if history.is_empty() {
+        return Err(PlotError::NoHistory);
+    }
+    fs::create_dir_all(out)?;
+
+    let mut by_day: BTreeMap<&str, Vec<&Record>> = BTreeMap::new();
+    for record in history {
+        by_day.entry(&record.label).or_default().push(record);
+    }
+
+    let mut written = Vec::new();
+    let mut latest = Vec::new();
+    for (label, records) in by_day {
+        let last_run = records.iter().map(|r| r.timestamp).max();
+        latest.push((
+            label,
+            records
+                .iter()
+                .copied()
+                .filter(|r| Some(r.timestamp) == last_run)
+                .collect(),
+        ));
+
+        let path = out.join(format!("{}.{}", label, format.extension()));
+        draw_to(&path, format, &Chart::Trend(label, records))?;
+        written.push(path);
+    }
+
+    let path = out.join(format!("all_days.{}", format.extension()));
+    draw_to(&path, format, &Chart::Latest(latest))?;
+    written.push(path);
+    Ok(written)
+}
+
+// Renders one log-log chart per day of the scaling series into out, returns the written files
+pub fn render_scaling(
+    series: &[Series],
+    out: &Path,
+    format: Format,
+) -> Result<Vec<PathBuf>, PlotError> {
+    fs::create_dir_all(out)?;
+    let mut written = Vec::new();
+    for (day, series) in &series.iter().chunk_by(|s| s.day) {
+        let series = series.filter(|s| !s.points.is_empty()).collect_vec();
+        if series.is_empty() {
+            continue;
+        }
+        let path = out.join(format!("scale_{}.{}", day, format.extension()));
+        draw_to(&path, format, &Chart::Scaling(day, series))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn draw_to(path: &Path, format: Format, chart: &Chart) -> Result<(), PlotError> {
+    match format {
+        Format::Svg => {
+            let root = SVGBackend::new(path, SIZE).into_drawing_area();
+            draw(&root, chart)?;
+            root.present()?;
+        }
+        Format::Png => {
+            let root = BitMapBackend::new(path, SIZE).into_drawing_area();
+            draw(&root, chart)?;
+            root.present()?;
+        }
+    }
+    Ok(())
+}
+
+fn millis(record: &Record) -> f64 {
+    record.stats.median.as_secs_f64() * 1e3
+}
+
+fn draw<DB: DrawingBackend>(root: &DrawingArea<DB, Shift>, chart: &Chart) -> Result<(), PlotError>
+where
+    DB::ErrorType: 'static,
+{
+    root.fill(&WHITE)?;
+    match chart {
+        Chart::Trend(label, records) => {
+            let runs: Vec<u64> = records
+                .iter()
+                .map(|r| r.timestamp)
+                .sorted()
+                .dedup()
+                .collect();
+            let max = records.iter().map(|r| millis(r)).fold(0.0, f64::max);
+            let mut chart = ChartBuilder::on(root)
+                .caption(
+                    format!("{}: median time per run", label),
+                    ("sans-serif", 28),
+                )
+                .margin(16)
+                .x_label_area_size(40)
+                .y_label_area_size(70)
+                .build_cartesian_2d(
+                    (0..runs.len() as u32 - 1).into_segmented(),
+                    0.0..max.max(1e-3) * 1.1,
+                )?;
+            chart
+                .configure_mesh()
+                .disable_x_mesh()
+                .x_desc("run")
+                .y_desc("ms")
+                .x_labels(runs.len().min(20))
+                .x_label_formatter(&|x| match x {
+                    SegmentValue::CenterOf(i) => (i + 1).to_string(),
+                    _ => String::new(),
+                })
+                .draw()?;
+
+            for (phase, color) in PHASES {
+                let points: Vec<_> = records
+                    .iter()
+                    .filter(|r| r.phase == phase)
+                    .filter_map(|r| {
+                        let run = runs.binary_search(&r.timestamp).ok()?;
+                        Some((SegmentValue::CenterOf(run as u32), millis(r)))
+                    })
+                    .collect();
+                chart
+                    .draw_series(LineSeries::new(
+                        points.iter().cloned(),
+                        color.stroke_width(2),
+                    ))?
+                    .label(phase.to_string())
+                    .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color));
+                chart.draw_series(
+                    points
+                        .iter()
+                        .map(|point| Circle::new(point.clone(), 3, color.filled())),
+                )?;
+            }
+            draw_legend(&mut chart)?;
+        }
+        Chart::Latest(days) => {
+            let totals: Vec<f64> = days
+                .iter()
+                .map(|(_, records)| records.iter().map(|r| millis(r)).sum())
+                .collect();
+            let max = totals.iter().copied().fold(0.0, f64::max);
+            let mut chart = ChartBuilder::on(root)
+                .caption("latest run per day", ("sans-serif", 28))
+                .margin(16)
+                .x_label_area_size(40)
+                .y_label_area_size(70)
+                .build_cartesian_2d(
+                    (0..days.len() as u32 - 1).into_segmented(),
+                    0.0..max.max(1e-3) * 1.1,
+                )?;
+            chart
+                .configure_mesh()
+                .disable_x_mesh()
+                .y_desc("ms")
+                .x_labels(days.len())
+                .x_label_formatter(&|x| match x {
+                    SegmentValue::CenterOf(i) => days
+                        .get(*i as usize)
+                        .map_or(String::new(), |(label, _)| label.to_string()),
+                    _ => String::new(),
+                })
+                .draw()?;
+
+            for (i, (phase, color)) in PHASES.into_iter().enumerate() {
+                let bars = days.iter().enumerate().filter_map(|(day, (_, records))| {
+                    // stacked on top of the phases before
+                    let below: f64 = PHASES[..i]
+                        .iter()
+                        .filter_map(|(p, _)| records.iter().find(|r| r.phase == *p))
+                        .map(|r| millis(r))
+                        .sum();
+                    let record = records.iter().find(|r| r.phase == phase)?;
+                    let day = day as u32;
+                    let mut bar = Rectangle::new(
+                        [
+                            (SegmentValue::Exact(day), below),
+                            (SegmentValue::Exact(day + 1), below + millis(record)),
+                        ],
+                        color.filled(),
+                    );
+                    bar.set_margin(0, 0, 6, 6);
+                    Some(bar)
+                });
+                chart
+                    .draw_series(bars)?
+                    .label(phase.to_string())
+                    .legend(move |(x, y)| {
+                        Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled())
+                    });
+            }
+            draw_legend(&mut chart)?;
+        }
+        Chart::Scaling(day, series) => {
+            let points = || series.iter().flat_map(|s| &s.points);
+            let (min_size, max_size) = points()
+                .map(|(size, _)| *size as f64)
+                .minmax()
+                .into_option()
+                .unwrap_or((1.0, 1.0));
+            let (min_time, max_time) = points()
+                .map(|(_, time)| time.as_secs_f64() * 1e3)
+                .minmax_by(f64::total_cmp)
+                .into_option()
+                .unwrap_or((1e-3, 1e-3));
+            // a log scale can't start at zero, and needs some room above and below
+            let min_time = min_time.max(1e-6);
+            let mut chart = ChartBuilder::on(root)
+                .caption(format!("{}: time over input size", day), ("sans-serif", 28))
+                .margin(16)
+                .x_label_area_size(40)
+                .y_label_area_size(70)
+                .build_cartesian_2d(
+                    // sizes usually double, so powers of two are at the ticks
+                    (min_size.max(1.0) / 1.2..max_size.max(1.0) * 1.2)
+                        .log_scale()
+                        .base(2.0),
+                    (min_time / 1.5..max_time.max(min_time) * 1.5).log_scale(),
+                )?;
+            chart
+                .configure_mesh()
+                .x_desc("size")
+                .y_desc("ms")
+                .x_label_formatter(&|size| format!("{:.0}", size))
+                .draw()?;
+
+            // phases by color, later variants of the day lighter
+            let variants = series.iter().map(|s| &s.label).unique().collect_vec();
+            for s in series {
+                let (_, color) = PHASES.into_iter().find(|(p, _)| *p == s.phase).unwrap();
+                let shade = variants.iter().position(|l| *l == &s.label).unwrap_or(0);
+                let color = color.mix(1.0 - 0.6 * shade as f64 / variants.len() as f64);
+                let points: Vec<_> = s
+                    .points
+                    .iter()
+                    .map(|(size, time)| (*size as f64, (time.as_secs_f64() * 1e3).max(min_time)))
+                    .collect();
+                let exponent = s.exponent().map_or("-".to_owned(), |k| format!("{:.2}", k));
+                chart
+                    .draw_series(LineSeries::new(
+                        points.iter().cloned(),
+                        color.stroke_width(2),
+                    ))?
+                    .label(format!("{} {} (size^{})", s.label, s.phase, exponent))
+                    .legend(move |(x, y)| PathElement::new([(x, y), (x + 20, y)], color));
+                chart.draw_series(
+                    points
+                        .iter()
+                        .map(|point| Circle::new(*point, 3, color.filled())),
+                )?;
+            }
+            draw_legend(&mut chart)?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_legend<'a, DB: DrawingBackend + 'a, CT: CoordTranslate>(
+    chart: &mut ChartContext<'a, DB, CT>,
+) -> Result<(), PlotError>
+where
+    DB::ErrorType: 'static,
+{
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperLeft)
+        .background_style(WHITE.mix(0.8))
+        .border_style(BLACK)
+        .draw()?;
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use crate::{
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, solution::DynSolution,
+};
+
+pub struct Variant {
+    pub name: &'static str,
+    pub solution: &'static dyn DynSolution,
+}
+
+/// All implementations of one day, the first variant is used unless another one is asked for
+pub struct Day {
+    pub day: u8,
+    pub label: &'static str,
+    pub variants: &'static [Variant],
+}
+
+macro_rules! day {
+    ($day:expr, $label:expr, $($name:expr => $solution:expr),+) => {
+        Day {
+            day: $day,
+            label: $label,
+            variants: &[$(Variant { name: $name, solution: &$solution }),+],
+        }
+    };
+    ($day:expr, $label:expr, $solution:expr) => {
+        day!($day, $label, "default" => $solution)
+    };
+}
+
+pub static DAYS: &[Day] = &[
+    day!(1, "day01", day01::Day01),
+    day!(2, "day02", day02::Day02),
+    day!(3, "day03", day03::Day03),
+    day!(4, "day04", day04::Day04),
+    day!(5, "day05", day05::Day05),
+    day!(6, "day06", day06::Day06),
+    day!(7, "day07", "par" => day07::Day07::PARALLEL, "seq" => day07::Day07::SEQUENTIAL),
+    day!(8, "day08", day08::Day08),
+    day!(9, "day09", day09::Day09),
+    day!(10, "day10", day10::Day10),
+    day!(11, "day11", "numbers" => day11::Day11, "digits" => day11::Day11Digits),
+    day!(12, "day12", day12::Day12),
+    day!(13, "day13", day13::Day13),
+    day!(14, "day14", day14::Day14),
+    day!(15, "day15", day15::Day15),
+    day!(16, "day16", day16::Day16),
+    day!(
+        17,
+        "day17",
+        "execute1" => day17::Day17::EXECUTE1,
+        "execute2" => day17::Day17::EXECUTE2,
+        "execute3" => day17::Day17::EXECUTE3
+    ),
+];
+
+pub fn get(day: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
+
+impl Day {
+    // None picks the default
+    pub fn variant(&self, name: Option<&str>) -> Option<&'static Variant> {
+        match name {
+            Some(name) => self.variants.iter().find(|v| v.name == name),
+            None => self.variants.first(),
+        }
+    }
+
+    pub fn is_default(&self, variant: &Variant) -> bool {
+        self.variants
+            .first()
+            .is_some_and(|v| v.name == variant.name)
+    }
+
+    // "day11" for the default variant, "day11-digits" for the others
+    pub fn label_of(&self, variant: &Variant) -> String {
+        if self.is_default(variant) {
+            self.label.to_owned()
+        } else {
+            format!("{}-{}", self.label, variant.name)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::Params;
+
+    #[test]
+    fn test_variants() {
+        let day11 = get(11).unwrap();
+        assert_eq!(day11.variant(None).unwrap().name, "numbers");
+        let digits = day11.variant(Some("digits")).unwrap();
+        assert_eq!(day11.label_of(digits), "day11-digits");
+        assert!(day11.variant(Some("par")).is_none());
+        assert!(get(0).is_none());
+    }
+
+    // inputs that used to panic in part 1 or 2, they are rejected when parsing
+    #[test]
+    fn test_bad_inputs() {
+        for (day, input) in [
+            (4, "XM\nAS\n"),
+            (15, "@\n\n<\n"),
+            (16, "SE\n"),
+            (16, "#\n##\n"),
+        ] {
+            for variant in get(day).unwrap().variants {
+                let params = Params::defaults(variant.solution.params());
+                assert!(
+                    variant.solution.parse(input, &params).is_err(),
+                    "day {} accepts {:?}",
+                    day,
+                    input
+                );
+            }
+        }
+    }
+}
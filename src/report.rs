@@ -0,0 +1,236 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use aoc24::{
+    alloc::Memory,
+    bench::Stats,
+    runner::Solved,
+    solution::{DayError, Phase},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Human readable lines
+    Text,
+    /// One json object per line and day
+    Json,
+    /// A header and one row per day
+    Csv,
+}
+
+// One day of a run for the machine readable formats.
+// Fields are only ever added at the end, so that output of different versions can be compared.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub input: String,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub timings: BTreeMap<Phase, Timing>,
+    pub error: Option<String>,
+    pub variant: String,
+    pub cached: bool,
+    // empty unless built with the alloc-stats feature
+    pub memory: BTreeMap<Phase, Memory>,
+}
+
+// durations in nanoseconds
+#[derive(Debug, Serialize)]
+pub struct Timing {
+    pub samples: usize,
+    pub min: u128,
+    pub median: u128,
+    pub mean: u128,
+    pub stddev: u128,
+    pub outliers: usize,
+}
+
+const PHASES: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+const TIMING_FIELDS: [&str; 6] = ["samples", "min", "median", "mean", "stddev", "outliers"];
+const MEMORY_FIELDS: [&str; 3] = ["allocations", "bytes", "peak"];
+
+impl Record {
+    pub fn new(
+        day: u8,
+        variant: &str,
+        input: &str,
+        result: &Result<Solved, DayError>,
+        cached: bool,
+    ) -> Record {
+        let (answers, timings, memory, error) = match result {
+            Ok(Solved {
+                answers,
+                timings,
+                memory,
+            }) => (
+                Some(answers),
+                timings
+                    .iter()
+                    .map(|(phase, stats)| (*phase, Timing::from(stats)))
+                    .collect(),
+                memory.iter().copied().collect(),
+                None,
+            ),
+            Err(e) => (None, BTreeMap::new(), BTreeMap::new(), Some(e.to_string())),
+        };
+        Record {
+            day,
+            input: input.to_owned(),
+            part1: answers.and_then(|a| a.0.as_ref()).map(ToString::to_string),
+            part2: answers.and_then(|a| a.1.as_ref()).map(ToString::to_string),
+            timings,
+            error,
+            variant: variant.to_owned(),
+            cached,
+            memory,
+        }
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string(self).expect("Records are always serializable")
+    }
+
+    pub fn csv_header() -> String {
+        let mut fields = vec![
+            "day".to_owned(),
+            "input".to_owned(),
+            "part1".to_owned(),
+            "part2".to_owned(),
+            "error".to_owned(),
+        ];
+        for phase in PHASES {
+            fields.extend(TIMING_FIELDS.iter().map(|f| format!("{}_{}", phase, f)));
+        }
+        fields.push("variant".to_owned());
+        fields.push("cached".to_owned());
+        for phase in PHASES {
+            fields.extend(MEMORY_FIELDS.iter().map(|f| format!("{}_{}", phase, f)));
+        }
+        fields.join(",")
+    }
+
+    // missing values are empty fields
+    pub fn csv(&self) -> String {
+        let mut fields = vec![
+            self.day.to_string(),
+            self.input.clone(),
+            self.part1.clone().unwrap_or_default(),
+            self.part2.clone().unwrap_or_default(),
+            self.error.clone().unwrap_or_default(),
+        ];
+        for phase in PHASES {
+            match self.timings.get(&phase) {
+                Some(t) => fields.extend([
+                    t.samples.to_string(),
+                    t.min.to_string(),
+                    t.median.to_string(),
+                    t.mean.to_string(),
+                    t.stddev.to_string(),
+                    t.outliers.to_string(),
+                ]),
+                None => fields.extend(TIMING_FIELDS.map(|_| String::new())),
+            }
+        }
+        fields.push(self.variant.clone());
+        fields.push(self.cached.to_string());
+        for phase in PHASES {
+            match self.memory.get(&phase) {
+                Some(m) => fields.extend([
+                    m.allocations.to_string(),
+                    m.bytes.to_string(),
+                    m.peak.to_string(),
+                ]),
+                None => fields.extend(MEMORY_FIELDS.map(|_| String::new())),
+            }
+        }
+        fields
+            .iter()
+            .map(|f| csv_field(f))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl From<&Stats> for Timing {
+    fn from(stats: &Stats) -> Self {
+        Timing {
+            samples: stats.samples,
+            min: stats.min.as_nanos(),
+            median: stats.median.as_nanos(),
+            mean: stats.mean.as_nanos(),
+            stddev: stats.stddev.as_nanos(),
+            outliers: stats.outliers,
+        }
+    }
+}
+
+// quoted if needed, day17's answer is a comma separated list
+fn csv_field(s: &str) -> Cow<'_, str> {
+    if s.contains([',', '"', '\n']) {
+        Cow::Owned(format!("\"{}\"", s.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use aoc24::solution::Answer;
+
+    #[test]
+    fn test_record() {
+        let solved = Ok(Solved {
+            answers: (Some(Answer::from("7,4,2")), Some(Answer::from(6usize))),
+            timings: vec![(Phase::Part1, Stats::new(&[Duration::from_nanos(1500)]))],
+            memory: vec![(
+                Phase::Part2,
+                Memory {
+                    allocations: 3,
+                    bytes: 64,
+                    peak: 32,
+                },
+            )],
+        });
+        let record = Record::new(17, "execute1", "input/day17.txt", &solved, false);
+        assert_eq!(
+            record.json(),
+            r#"{"day":17,"input":"input/day17.txt","part1":"7,4,2","part2":"6","timings":{"part1":{"samples":1,"min":1500,"median":1500,"mean":1500,"stddev":0,"outliers":0}},"error":null,"variant":"execute1","cached":false,"memory":{"part2":{"allocations":3,"bytes":64,"peak":32}}}"#
+        );
+        assert_eq!(
+            record.csv(),
+            r#"17,input/day17.txt,"7,4,2",6,,,,,,,,1,1500,1500,1500,0,0,,,,,,,execute1,false,,,,,,,3,64,32"#
+        );
+        assert_eq!(
+            Record::csv_header().split(',').count(),
+            record.csv().split(',').count() - 2
+        );
+    }
+
+    #[test]
+    fn test_single_part() {
+        let solved = Ok(Solved {
+            answers: (None, Some(Answer::from(6usize))),
+            timings: vec![],
+            memory: vec![],
+        });
+        let record = Record::new(6, "default", "day06.txt", &solved, true);
+        assert!(record.json().contains(r#""part1":null,"part2":"6""#));
+        assert!(record.csv().starts_with("6,day06.txt,,6,,"));
+    }
+
+    #[test]
+    fn test_error() {
+        let record = Record::new(6, "default", "day06.txt", &Err(DayError::NoSolution), false);
+        assert!(record.json().ends_with(
+            r#""timings":{},"error":"No solution found","variant":"default","cached":false,"memory":{}}"#
+        ));
+        assert!(record
+            .csv()
+            .starts_with("6,day06.txt,,,No solution found,,"));
+    }
+}
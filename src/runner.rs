@@ -0,0 +1,379 @@
+use std::{
+    any::Any,
+    fs, io,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    alloc::{self, Memory},
+    bench::{self, BenchConfig, Stats},
+    params::Params,
+    solution::{Answer, DayError, DynSolution, Phase},
+};
+
+pub struct Solved {
+    // None for a part that was not asked for
+    pub answers: (Option<Answer>, Option<Answer>),
+    pub timings: Vec<(Phase, Stats)>,
+    // empty unless built with the alloc-stats feature
+    pub memory: Vec<(Phase, Memory)>,
+}
+
+/// Reads the whole file, or stdin if path is "-"
+pub fn read_input(path: &str) -> Result<String, DayError> {
+    let (path, read) = match path {
+        "-" => ("stdin", io::read_to_string(io::stdin())),
+        _ => (path, fs::read_to_string(path)),
+    };
+    read.map_err(|source| DayError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+/// Every phase is benchmarked on its own, the parts share the last parsed input.
+/// part is 1 or 2 to only solve that part, None solves both.
+/// A solution that panics fails with `DayError::Panicked` and the message of the panic.
+pub fn solve(
+    solution: &dyn DynSolution,
+    input: &str,
+    params: &Params,
+    config: &BenchConfig,
+    part: Option<u8>,
+) -> Result<Solved, DayError> {
+    // nothing is shared with the solution that a panic could leave half updated
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        solve_unguarded(solution, input, params, config, part)
+    }))
+    .unwrap_or_else(|payload| Err(DayError::Panicked(panic_message(payload))))
+}
+
+// panic! with a literal gives a &str, with format arguments a String
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast_ref::<&str>() {
+            Some(message) => (*message).to_owned(),
+            None => "no message".to_owned(),
+        },
+    }
+}
+
+fn solve_unguarded(
+    solution: &dyn DynSolution,
+    input: &str,
+    params: &Params,
+    config: &BenchConfig,
+    part: Option<u8>,
+) -> Result<Solved, DayError> {
+    let mut solved = Solved {
+        answers: (None, None),
+        timings: vec![],
+        memory: vec![],
+    };
+    let parsed = solved.run(Phase::Parse, config, || solution.parse(input, params))?;
+    if part != Some(2) {
+        solved.answers.0 = Some(solved.run(Phase::Part1, config, || solution.part1(&parsed))?);
+    }
+    if part != Some(1) {
+        solved.answers.1 = Some(solved.run(Phase::Part2, config, || solution.part2(&parsed))?);
+    }
+    Ok(solved)
+}
+
+/// Like `solve`, but on a worker thread that is given up on once budget has passed.
+/// The budget covers all runs of all phases. A worker can't be stopped,
+/// one that timed out keeps running in the background until the process ends.
+pub fn solve_within(
+    budget: Duration,
+    solution: &'static dyn DynSolution,
+    input: Arc<str>,
+    params: Params,
+    config: BenchConfig,
+    part: Option<u8>,
+) -> Result<Solved, DayError> {
+    on_worker(budget, solution, input, params, config, part, ())
+}
+
+/// Limits the workers of `solve_within` for processes that don't end soon, like a server.
+/// Workers that timed out count until they are done, when all are taken `solve_within`
+/// fails with `DayError::Busy` instead of starting one more.
+pub struct Workers {
+    limit: usize,
+    running: Arc<AtomicUsize>,
+}
+
+// held by a worker, gives its place back when the worker is done
+struct Permit(Arc<AtomicUsize>);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl Workers {
+    pub fn new(limit: usize) -> Workers {
+        Workers {
+            limit,
+            running: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn solve_within(
+        &self,
+        budget: Duration,
+        solution: &'static dyn DynSolution,
+        input: Arc<str>,
+        params: Params,
+        config: BenchConfig,
+        part: Option<u8>,
+    ) -> Result<Solved, DayError> {
+        let taken = self
+            .running
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < self.limit).then_some(n + 1)
+            });
+        if taken.is_err() {
+            return Err(DayError::Busy(self.limit));
+        }
+        let permit = Permit(self.running.clone());
+        on_worker(budget, solution, input, params, config, part, permit)
+    }
+}
+
+// keep is dropped when the worker is done, not when it is given up on
+fn on_worker<K: Send + 'static>(
+    budget: Duration,
+    solution: &'static dyn DynSolution,
+    input: Arc<str>,
+    params: Params,
+    config: BenchConfig,
+    part: Option<u8>,
+    keep: K,
+) -> Result<Solved, DayError> {
+    let (send, receive) = mpsc::channel();
+    let start = Instant::now();
+    thread::Builder::new()
+        .name("solver".to_owned())
+        .spawn(move || {
+            let result = solve(solution, &input, &params, &config, part);
+            // before sending, so that it is gone when the caller has the result
+            drop(keep);
+            // nobody listens anymore after a timeout
+            let _ = send.send(result);
+        })
+        .map_err(|source| DayError::Io {
+            path: "worker thread".to_owned(),
+            source,
+        })?;
+    match receive.recv_timeout(budget) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(DayError::Timeout(start.elapsed())),
+        // solve catches the panics of solutions, so this is hardly ever the case
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(DayError::Panicked(
+            "the worker ended without an answer".to_owned(),
+        )),
+    }
+}
+
+impl Solved {
+    // benchmarks one phase and records its timings and allocations
+    fn run<R>(
+        &mut self,
+        phase: Phase,
+        config: &BenchConfig,
+        f: impl FnMut() -> Result<R, DayError>,
+    ) -> Result<R, DayError> {
+        let (result, memory) = alloc::measure(|| bench::run(config, f));
+        let (result, samples) = result?;
+        self.timings.push((phase, Stats::new(&samples)));
+        if alloc::ENABLED {
+            let runs = config.warmup + samples.len();
+            self.memory.push((phase, memory.per_run(runs)));
+        }
+        Ok(result)
+    }
+}
+
+/// "2s" for every day, or "6=30s" and "10-14=1s" for some days
+pub fn parse_budget(s: &str) -> Result<(Option<RangeInclusive<u8>>, Duration), String> {
+    match s.split_once('=') {
+        Some((days, budget)) => Ok((Some(parse_day_range(days)?), bench::parse_duration(budget)?)),
+        None => Ok((None, bench::parse_duration(s)?)),
+    }
+}
+
+/// The budget of day out of those given with `parse_budget`, later ones win
+pub fn budget_for(budgets: &[(Option<RangeInclusive<u8>>, Duration)], day: u8) -> Option<Duration> {
+    budgets
+        .iter()
+        .rev()
+        .find(|(days, _)| days.as_ref().is_none_or(|days| days.contains(&day)))
+        .map(|&(_, budget)| budget)
+}
+
+/// "5" or "10-14", days start at 1
+pub fn parse_day_range(s: &str) -> Result<RangeInclusive<u8>, String> {
+    let day = |d: &str| match d.trim().parse() {
+        Ok(0) | Err(_) => Err(format!("not a day: {}", d)),
+        Ok(d) => Ok(d),
+    };
+    let range = match s.split_once('-') {
+        Some((first, last)) => day(first)?..=day(last)?,
+        None => day(s)?..=day(s)?,
+    };
+    if range.is_empty() {
+        return Err(format!("empty range: {}", s));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::{Answer, Solution};
+
+    struct Sleepy;
+
+    impl Solution for Sleepy {
+        type Input = Duration;
+
+        fn parse(&self, input: &str, _: &Params) -> Result<Self::Input, DayError> {
+            Ok(Duration::from_millis(input.parse()?))
+        }
+
+        fn part1(&self, nap: &Self::Input) -> Result<Answer, DayError> {
+            thread::sleep(*nap);
+            Ok(Answer::from(1usize))
+        }
+
+        fn part2(&self, _: &Self::Input) -> Result<Answer, DayError> {
+            Err(DayError::NoSolution)
+        }
+    }
+
+    #[test]
+    fn test_solve_within() {
+        let config = BenchConfig {
+            warmup: 0,
+            until: crate::bench::Until::Iterations(1),
+        };
+        let solve = |budget, input: &str| {
+            solve_within(
+                budget,
+                &Sleepy,
+                input.into(),
+                Params::default(),
+                config,
+                Some(1),
+            )
+        };
+        let solved = solve(Duration::from_secs(10), "0").unwrap();
+        assert_eq!(solved.answers.0, Some(Answer::from(1usize)));
+        assert!(matches!(
+            solve(Duration::from_millis(10), "5000"),
+            Err(DayError::Timeout(_))
+        ));
+        assert!(matches!(
+            solve(Duration::from_secs(10), "x"),
+            Err(DayError::WrongNumberFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_workers() {
+        let config = BenchConfig {
+            warmup: 0,
+            until: crate::bench::Until::Iterations(1),
+        };
+        let workers = Workers::new(1);
+        let solve = |input: &str| {
+            let budget = Duration::from_millis(50);
+            workers.solve_within(
+                budget,
+                &Sleepy,
+                input.into(),
+                Params::default(),
+                config,
+                Some(1),
+            )
+        };
+        assert!(solve("0").is_ok());
+        assert!(matches!(solve("300"), Err(DayError::Timeout(_))));
+        // the worker that timed out still runs
+        assert!(matches!(solve("0"), Err(DayError::Busy(1))));
+        thread::sleep(Duration::from_millis(500));
+        assert!(solve("0").is_ok());
+    }
+
+    struct Panicky;
+
+    impl Solution for Panicky {
+        type Input = ();
+
+        fn parse(&self, _: &str, _: &Params) -> Result<Self::Input, DayError> {
+            Ok(())
+        }
+
+        fn part1(&self, _: &Self::Input) -> Result<Answer, DayError> {
+            panic!("part 1 panics on purpose")
+        }
+
+        fn part2(&self, _: &Self::Input) -> Result<Answer, DayError> {
+            Ok(Answer::from(2usize))
+        }
+    }
+
+    #[test]
+    fn test_solve_catches_panics() {
+        let config = BenchConfig {
+            warmup: 0,
+            until: crate::bench::Until::Iterations(1),
+        };
+        let solve = |part| solve(&Panicky, "", &Params::default(), &config, part);
+        assert!(matches!(
+            solve(None),
+            Err(DayError::Panicked(message)) if message == "part 1 panics on purpose"
+        ));
+        assert_eq!(panic_message(Box::new(format!("{} left", 2))), "2 left");
+        assert_eq!(panic_message(Box::new(7)), "no message");
+        assert_eq!(
+            solve(Some(2)).unwrap().answers.1,
+            Some(Answer::from(2usize))
+        );
+    }
+
+    #[test]
+    fn test_parse_day_range() {
+        assert_eq!(parse_day_range("5"), Ok(5..=5));
+        assert_eq!(parse_day_range("10-14"), Ok(10..=14));
+        assert!(parse_day_range("0").is_err());
+        assert!(parse_day_range("14-10").is_err());
+        assert!(parse_day_range("1-").is_err());
+        assert!(parse_day_range("x").is_err());
+    }
+
+    #[test]
+    fn test_parse_budget() {
+        assert_eq!(parse_budget("2s"), Ok((None, Duration::from_secs(2))));
+        assert_eq!(
+            parse_budget("10-14=500ms"),
+            Ok((Some(10..=14), Duration::from_millis(500)))
+        );
+        assert!(parse_budget("6=").is_err());
+        assert!(parse_budget("0=1s").is_err());
+
+        let budgets = [parse_budget("2s").unwrap(), parse_budget("6=30s").unwrap()];
+        assert_eq!(budget_for(&budgets, 6), Some(Duration::from_secs(30)));
+        assert_eq!(budget_for(&budgets, 7), Some(Duration::from_secs(2)));
+        assert_eq!(budget_for(&budgets[1..], 7), None);
+    }
+}
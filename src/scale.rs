@@ -0,0 +1,197 @@
+use std::{path::Path, process::ExitCode, sync::Arc, time::Duration};
+
+use itertools::Itertools;
+
+use aoc24::{
+    generate,
+    params::Params,
+    registry::{Day, Variant},
+    runner,
+    solution::Phase,
+};
+
+use crate::{plot, Args};
+
+// How the time of a phase grows with the size of generated inputs. The exponent k of
+// time ~ size^k is the slope of the least squares line through the log-log points.
+// size is whatever the generator of the day takes, the side of the grid for grid days,
+// so a solution linear in the cells of a grid has an exponent of about 2.
+
+// one phase of one variant across sizes
+pub struct Series {
+    // the day, series of the same day share a chart
+    pub day: &'static str,
+    // the day and variant
+    pub label: String,
+    pub phase: Phase,
+    // median time per size, in the order of the sizes
+    pub points: Vec<(usize, Duration)>,
+}
+
+impl Series {
+    pub fn exponent(&self) -> Option<f64> {
+        fit(&self.points)
+    }
+}
+
+// None with less than two distinct sizes, sizes or times of zero are left out
+pub fn fit(points: &[(usize, Duration)]) -> Option<f64> {
+    let logs = points
+        .iter()
+        .filter(|(size, time)| *size > 0 && !time.is_zero())
+        .map(|(size, time)| ((*size as f64).ln(), time.as_secs_f64().ln()))
+        .collect_vec();
+    let n = logs.len() as f64;
+    let mean_x = logs.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = logs.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx: f64 = logs.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let sxy: f64 = logs.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+    // also false for NaN, when there are no points at all
+    if sxx > 1e-12 {
+        Some(sxy / sxx)
+    } else {
+        None
+    }
+}
+
+// a line per series with its exponent and the time for every size
+pub fn print(sizes: &[usize], series: &[Series]) {
+    let width = series
+        .iter()
+        .map(|s| s.label.len())
+        .max()
+        .unwrap_or(0)
+        .max(5)
+        + 2;
+    println!(
+        "{:<width$} {:<6} {:>8}  {}",
+        "label",
+        "phase",
+        "exponent",
+        sizes.iter().map(|s| format!("{:>10}", s)).join(" ")
+    );
+    for s in series {
+        let exponent = s.exponent().map_or("-".to_owned(), |k| format!("{:.2}", k));
+        let times = sizes
+            .iter()
+            .map(|size| match s.points.iter().find(|(n, _)| n == size) {
+                Some((_, time)) => format!("{:>10}", format!("{:.2?}", time)),
+                None => format!("{:>10}", "-"),
+            })
+            .join(" ");
+        println!(
+            "{:<width$} {:<6} {:>8}  {}",
+            s.label,
+            s.phase.to_string(),
+            exponent,
+            times
+        );
+    }
+}
+
+// times every selected variant on the generated inputs of every size, prints the series
+// and draws them, fails if a size fails
+pub fn run(
+    args: &Args,
+    selected: &[(&'static Day, Vec<&'static Variant>)],
+    sizes: &[usize],
+    seed: u64,
+    out: &Path,
+    format: plot::Format,
+) -> ExitCode {
+    let sizes = sizes.iter().copied().sorted().dedup().collect_vec();
+    let config = args.bench_config();
+    let mut series = Vec::new();
+    let mut failed = Vec::new();
+    for (day, variants) in selected {
+        if !matches!(generate::input(day.day, 1, seed), Ok(Some(_))) {
+            println!("{} has no generator", day.label);
+            continue;
+        }
+        for variant in variants {
+            let label = day.label_of(variant);
+            let mut phases: Vec<Series> = Vec::new();
+            for &size in &sizes {
+                // the generator of the default variant, so that all variants get the same inputs
+                let Ok(Some(input)) = generate::input(day.day, size, seed) else {
+                    break;
+                };
+                let params = Params::defaults(variant.solution.params());
+                let solved = match args.budget(day.day) {
+                    Some(budget) => runner::solve_within(
+                        budget,
+                        variant.solution,
+                        Arc::from(input),
+                        params,
+                        config,
+                        args.part,
+                    ),
+                    None => runner::solve(variant.solution, &input, &params, &config, args.part),
+                };
+                let solved = match solved {
+                    Ok(solved) => solved,
+                    Err(e) => {
+                        println!("{} size {}: {}", label, size, e);
+                        failed.push(format!("{} size {}", label, size));
+                        break;
+                    }
+                };
+                for (phase, stats) in solved.timings {
+                    match phases.iter_mut().find(|s| s.phase == phase) {
+                        Some(s) => s.points.push((size, stats.median)),
+                        None => phases.push(Series {
+                            day: day.label,
+                            label: label.clone(),
+                            phase,
+                            points: vec![(size, stats.median)],
+                        }),
+                    }
+                }
+            }
+            series.extend(phases);
+        }
+    }
+
+    print(&sizes, &series);
+    match plot::render_scaling(&series, out, format) {
+        Ok(paths) => {
+            for path in paths {
+                println!("Wrote {}", path.display());
+            }
+        }
+        Err(e) => {
+            println!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    }
+    if failed.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        println!("Failed: {}", failed.join(", "));
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let points = |k: f64| {
+            [10, 20, 40, 80]
+                .map(|n| (n, Duration::from_secs_f64(1e-3 * (n as f64).powf(k))))
+                .to_vec()
+        };
+        for k in [1.0, 2.0, 1.5] {
+            assert!((fit(&points(k)).unwrap() - k).abs() < 1e-6);
+        }
+        assert_eq!(fit(&[]), None);
+        assert_eq!(fit(&[(10, Duration::from_millis(1))]), None);
+        // zero times can't be on a log scale
+        assert_eq!(
+            fit(&[(10, Duration::ZERO), (20, Duration::from_millis(1))]),
+            None
+        );
+    }
+}
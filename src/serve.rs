@@ -0,0 +1,247 @@
+use std::{ops::RangeInclusive, sync::Arc, time::Duration};
+
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use aoc24::{bench::BenchConfig, params::Params, registry, runner, solution::DayError};
+
+use crate::report::Record;
+
+// A local http service, so that other tools can use the solutions without running the binary.
+// `POST /day/14?part=1&variant=default&width=11` solves the body as the input of day 14,
+// every query argument other than part and variant is a parameter.
+// Answers are the records of --format json, a request that can't be solved is answered
+// with a 4xx status and {"error": ...}, also one whose solution panics.
+// A request that runs out of its budget is answered with 504, but its solution keeps running,
+// so only so many of them are solved at the same time and the rest is answered with 503.
+pub struct Service {
+    pub config: BenchConfig,
+    pub budgets: Vec<(Option<RangeInclusive<u8>>, Duration)>,
+    pub workers: runner::Workers,
+}
+
+// what to solve the input with, from the query or a line of the batch mode
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Query {
+    pub part: Option<u8>,
+    pub variant: Option<String>,
+    pub params: Vec<(String, String)>,
+}
+
+pub fn error(status: u16, message: impl ToString) -> (u16, String) {
+    (status, json!({ "error": message.to_string() }).to_string())
+}
+
+// %2C is a comma and + a space, invalid escapes are kept as they are
+fn decode(s: &str) -> String {
+    let s = s.replace('+', " ");
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (b, escaped) {
+            (b'%', Some(decoded)) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(b);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn parse_query(query: &str) -> Result<Query, String> {
+    let mut parsed = Query::default();
+    for pair in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (key, value) = (decode(key), decode(value));
+        match key.as_str() {
+            "part" => match value.parse() {
+                Ok(part) => parsed.part = Some(part),
+                Err(_) => return Err(format!("part is 1 or 2, not {}", value)),
+            },
+            "variant" => parsed.variant = Some(value),
+            _ => parsed.params.push((key, value)),
+        }
+    }
+    Ok(parsed)
+}
+
+impl Service {
+    // answers one request after the other, so that solutions don't compete for the cores
+    pub fn run(&self, server: &Server) {
+        for request in server.incoming_requests() {
+            self.reply(request);
+        }
+    }
+
+    fn reply(&self, mut request: Request) {
+        let mut body = String::new();
+        let (status, json) = match request.as_reader().read_to_string(&mut body) {
+            Ok(_) => self.handle(request.method(), request.url(), &body),
+            Err(e) => error(400, format!("Cannot read body: {}", e)),
+        };
+        let content_type =
+            Header::from_bytes("Content-Type", "application/json").expect("Header is valid");
+        let response = Response::from_string(json)
+            .with_status_code(status)
+            .with_header(content_type);
+        // the client is gone, nothing else to do
+        let _ = request.respond(response);
+    }
+
+    // the status and json body of the response
+    fn handle(&self, method: &Method, url: &str, body: &str) -> (u16, String) {
+        let (path, query) = url.split_once('?').unwrap_or((url, ""));
+        let Some(day) = path.strip_prefix("/day/") else {
+            return error(404, format!("No such path {}, try POST /day/{{n}}", path));
+        };
+        if *method != Method::Post {
+            return error(405, format!("Use POST instead of {}", method));
+        }
+        let Ok(day) = day.parse() else {
+            return error(404, format!("There is no solution for day {}", day));
+        };
+        match parse_query(query) {
+            Ok(query) => self.solve(day, &query, body),
+            Err(e) => error(400, e),
+        }
+    }
+
+    /// Solves input with a day the way the query asks for, the response is a record
+    /// or {"error": ...} with a 4xx status
+    pub fn solve(&self, day: u8, query: &Query, input: &str) -> (u16, String) {
+        let Some(day) = registry::get(day) else {
+            return error(404, DayError::UnknownDay(day));
+        };
+        if let Some(part @ (0 | 3..)) = query.part {
+            return error(400, format!("part is 1 or 2, not {}", part));
+        }
+        let Some(variant) = day.variant(query.variant.as_deref()) else {
+            return error(
+                404,
+                format!(
+                    "{} has no variant {}",
+                    day.label,
+                    query.variant.as_deref().unwrap_or_default()
+                ),
+            );
+        };
+        let params = match Params::resolve(variant.solution.params(), &query.params) {
+            Ok(params) => params,
+            Err(e) => return error(400, e),
+        };
+
+        let result = match runner::budget_for(&self.budgets, day.day) {
+            Some(budget) => self.workers.solve_within(
+                budget,
+                variant.solution,
+                Arc::from(input),
+                params,
+                self.config,
+                query.part,
+            ),
+            None => runner::solve(variant.solution, input, &params, &self.config, query.part),
+        };
+        let status = match result {
+            Ok(_) => 200,
+            Err(DayError::Timeout(_)) => 504,
+            Err(DayError::Busy(_)) => 503,
+            Err(_) => 422,
+        };
+        let record = Record::new(day.day, variant.name, "request", &result, false);
+        (status, record.json())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Read, Write},
+        net::TcpStream,
+        thread,
+    };
+
+    use aoc24::bench::Until;
+
+    use super::*;
+
+    fn service() -> Service {
+        Service {
+            config: BenchConfig {
+                warmup: 0,
+                until: Until::Iterations(1),
+            },
+            budgets: vec![],
+            workers: runner::Workers::new(1),
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = parse_query("part=2&list=1%2C2+&variant=digits&flag").unwrap();
+        assert_eq!(
+            query,
+            Query {
+                part: Some(2),
+                variant: Some("digits".to_owned()),
+                params: vec![
+                    ("list".to_owned(), "1,2 ".to_owned()),
+                    ("flag".to_owned(), String::new())
+                ],
+            }
+        );
+        assert_eq!(decode("100%"), "100%");
+        assert!(parse_query("part=x").is_err());
+    }
+
+    #[test]
+    fn test_handle() {
+        let service = service();
+        let (status, json) = service.handle(&Method::Post, "/day/11?part=1", "125 17");
+        assert_eq!(status, 200);
+        assert!(json.contains(r#""part1":"55312","part2":null"#), "{}", json);
+
+        let status = |method, url| service.handle(&method, url, "").0;
+        assert_eq!(status(Method::Get, "/day/11"), 405);
+        assert_eq!(status(Method::Post, "/"), 404);
+        assert_eq!(status(Method::Post, "/day/26"), 404);
+        assert_eq!(status(Method::Post, "/day/11?part=3"), 400);
+        assert_eq!(status(Method::Post, "/day/11?variant=nope"), 404);
+        assert_eq!(status(Method::Post, "/day/11?blinks1=x"), 400);
+        assert_eq!(status(Method::Post, "/day/11?height=7"), 400);
+        assert_eq!(service.handle(&Method::Post, "/day/11", "x").0, 422);
+        // used to panic and take the server down with it
+        assert_eq!(service.handle(&Method::Post, "/day/4", "x").0, 422);
+    }
+
+    #[test]
+    fn test_loopback() {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let addr = server.server_addr().to_ip().unwrap();
+        // the server thread ends with the test process
+        thread::spawn(move || service().run(&server));
+
+        let body = "p=0,4 v=3,-3\np=6,3 v=-1,-3\n";
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST /day/14?part=1&width=11&height=7 HTTP/1.1\r\n\
+             Host: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+        assert!(response.contains("application/json"), "{}", response);
+        assert!(response.contains(r#""day":14"#), "{}", response);
+    }
+}
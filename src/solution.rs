@@ -0,0 +1,228 @@
+use std::{any::Any, fmt, io, num::ParseIntError, time::Duration};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    day07::ParseEquationError,
+    day11::ParseStoneError,
+    day13::MachineParseError,
+    day14::RobotParseError,
+    generate::Rng,
+    grid::GridParseError,
+    params::{Param, ParamError, Params},
+};
+
+/// The answer of one part of a puzzle, in whatever type is natural for the day
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Big(i128),
+    Text(String),
+}
+
+/// The input is parsed once and then shared between both parts
+pub trait Solution {
+    type Input;
+
+    /// Tunable constants of the puzzle, their values are passed to parse
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Self::Input, DayError>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer, DayError>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer, DayError>;
+
+    /// A random input for stress tests that parse accepts with the default parameters.
+    /// size is about the number of lines or entries, None for days without a generator
+    fn generate(&self, _size: usize, _rng: &mut Rng) -> Option<String> {
+        None
+    }
+}
+
+pub type Parsed = Box<dyn Any + Send + Sync>;
+
+/// Solution with the input type erased, so that all days can be kept in one table
+pub trait DynSolution: Send + Sync {
+    fn params(&self) -> &'static [Param];
+    fn parse(&self, input: &str, params: &Params) -> Result<Parsed, DayError>;
+    fn part1(&self, parsed: &Parsed) -> Result<Answer, DayError>;
+    fn part2(&self, parsed: &Parsed) -> Result<Answer, DayError>;
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String>;
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Send + Sync,
+    S::Input: Send + Sync + 'static,
+{
+    fn params(&self) -> &'static [Param] {
+        Solution::params(self)
+    }
+
+    fn parse(&self, input: &str, params: &Params) -> Result<Parsed, DayError> {
+        Ok(Box::new(Solution::parse(self, input, params)?))
+    }
+
+    fn part1(&self, parsed: &Parsed) -> Result<Answer, DayError> {
+        Solution::part1(self, downcast::<S>(parsed))
+    }
+
+    fn part2(&self, parsed: &Parsed) -> Result<Answer, DayError> {
+        Solution::part2(self, downcast::<S>(parsed))
+    }
+
+    fn generate(&self, size: usize, rng: &mut Rng) -> Option<String> {
+        Solution::generate(self, size, rng)
+    }
+}
+
+fn downcast<S: Solution>(parsed: &Parsed) -> &S::Input
+where
+    S::Input: 'static,
+{
+    parsed
+        .downcast_ref()
+        .expect("Input was parsed by a different solution")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+impl std::str::FromStr for Phase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Phase::Parse),
+            "part1" => Ok(Phase::Part1),
+            "part2" => Ok(Phase::Part2),
+            _ => Err(format!("unknown phase: {}", s)),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DayError {
+    #[error("Cannot read {path}: {source}")]
+    Io { path: String, source: io::Error },
+    #[error("Wrong number format: {0}")]
+    WrongNumberFormat(#[from] ParseIntError),
+    #[error("Bad grid: {0}")]
+    Grid(#[from] GridParseError),
+    #[error("Bad equation: {0}")]
+    Equation(#[from] ParseEquationError),
+    #[error("Bad stone: {0}")]
+    Stone(#[from] ParseStoneError),
+    #[error("Bad machine: {0}")]
+    Machine(#[from] MachineParseError),
+    #[error("Bad robot: {0}")]
+    Robot(#[from] RobotParseError),
+    #[error("Unexpected input: {0}")]
+    WrongFormat(&'static str),
+    #[error("Bad parameter: {0}")]
+    Param(#[from] ParamError),
+    #[error("Unexpected example header line {0}, expected part1, part2 or param")]
+    ExampleHeader(String),
+    #[error("No solution found")]
+    NoSolution,
+    #[error("The numbers got too large to compute with")]
+    Overflow,
+    #[error("There is no solution for day {0}")]
+    UnknownDay(u8),
+    #[error("Timed out after {0:.2?}")]
+    Timeout(Duration),
+    #[error("All {0} workers are busy")]
+    Busy(usize),
+    #[error("Panicked: {0}")]
+    Panicked(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Unsigned(value as u64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Self {
+        Answer::Unsigned(value.into())
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Self {
+        Answer::Unsigned(value)
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Self {
+        Answer::Signed(value.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Self {
+        Answer::Signed(value)
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(42usize).to_string(), "42");
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(i128::MAX).to_string(), i128::MAX.to_string());
+        assert_eq!(Answer::from("0,1,2").to_string(), "0,1,2");
+    }
+}
@@ -1,5 +1,6 @@
 use std::{collections::HashSet, hash::Hash};
 
+/// Iterator over the first occurrence of every item, see [`IteratorExt::uniques`]
 // upside: can be chained nicely, is lazy
 // downside: need clonable, up to 2x memory requirements
 pub struct Uniques<I, T>
@@ -32,17 +33,13 @@ where
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(item) = self.input.next() {
-            if self.seen.insert(item.clone()) {
-                return Some(item);
-            }
-        }
-        None
+        self.input.find(|item| self.seen.insert(item.clone()))
     }
 }
 
-// Extension trait to make it convenient to use
+/// Extension trait to make it convenient to use
 pub trait IteratorExt: Iterator {
+    /// Skips items that were already seen, keeping the order of first occurrence
     fn uniques(self) -> Uniques<Self, Self::Item>
     where
         Self: Sized,
@@ -76,6 +73,6 @@ mod tests {
     fn test_empty() {
         let input: Vec<i32> = vec![];
         let result: Vec<i32> = input.into_iter().uniques().collect();
-        assert_eq!(result, vec![]);
+        assert!(result.is_empty());
     }
 }
@@ -0,0 +1,86 @@
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    process::ExitCode,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use itertools::Itertools;
+
+use aoc24::registry::{Day, Variant};
+
+use crate::{input_path, report::Format, solve_days, Args};
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+// when the file was changed and a hash of its content, None if it can't be read
+fn fingerprint(path: &str) -> Option<(SystemTime, u64)> {
+    let modified = fs::metadata(path).and_then(|m| m.modified()).ok()?;
+    let mut hasher = DefaultHasher::new();
+    fs::read(path).ok()?.hash(&mut hasher);
+    Some((modified, hasher.finish()))
+}
+
+// polls the inputs of the selected days and solves them again when one changes, never returns
+pub fn run(args: &Args, selected: &[(&'static Day, Vec<&'static Variant>)], all: bool) -> ExitCode {
+    if args.input.as_deref() == Some("-") {
+        say!(
+            args.format,
+            "--watch needs an input file, stdin can't be read again"
+        );
+        return ExitCode::FAILURE;
+    }
+    let paths = selected
+        .iter()
+        .map(|(day, _)| input_path(day, &args.input_base, args))
+        .collect_vec();
+    let mut seen = None;
+    loop {
+        // taken before solving, so that changes made meanwhile are noticed
+        let current = paths.iter().map(|path| fingerprint(path)).collect_vec();
+        if seen.as_ref() != Some(&current) {
+            if args.format == Format::Text {
+                // clear the screen and move to the top left
+                print!("\x1b[2J\x1b[H");
+            }
+            solve_days(args, selected, all);
+            say!(
+                args.format,
+                "Watching {} for changes, Ctrl-C to stop",
+                if paths.len() == 1 {
+                    paths[0].clone()
+                } else {
+                    format!("{} inputs", paths.len())
+                }
+            );
+            seen = Some(current);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let path =
+            std::env::temp_dir().join(format!("aoc24_fingerprint_{}.txt", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert_eq!(fingerprint(path), None);
+
+        fs::write(path, "1 2\n").unwrap();
+        let first = fingerprint(path).unwrap();
+        assert_eq!(fingerprint(path), Some(first));
+        // same length and maybe the same modification time, the content still tells
+        fs::write(path, "1 3\n").unwrap();
+        let second = fingerprint(path).unwrap();
+        assert_ne!(second, first);
+        assert_eq!(fingerprint(path), Some(second));
+
+        fs::remove_file(path).unwrap();
+        assert_eq!(fingerprint(path), None);
+    }
+}
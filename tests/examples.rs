@@ -0,0 +1,39 @@
+use std::path::Path;
+
+use aoc24::examples::{self, Example, Verdict};
+
+// the same examples as `aoc24 examples -i tests/examples`
+#[test]
+fn test_examples() {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/examples");
+    let paths = examples::discover(&dir).unwrap();
+    assert!(!paths.is_empty(), "No examples in {}", dir.display());
+
+    let mut failures = Vec::new();
+    for path in paths {
+        let name = path.file_name().unwrap().to_string_lossy().into_owned();
+        let runs = match Example::load(&path).and_then(|e| e.run()) {
+            Ok(runs) => runs,
+            Err(e) => {
+                failures.push(format!("{}: {}", name, e));
+                continue;
+            }
+        };
+        for (label, verdicts) in runs {
+            match verdicts {
+                Ok(verdicts) => {
+                    for (part, verdict) in (1..).zip(verdicts) {
+                        if let Verdict::Fail { expected, actual } = verdict {
+                            failures.push(format!(
+                                "{} {} part {}: expected {}, got {}",
+                                name, label, part, expected, actual
+                            ));
+                        }
+                    }
+                }
+                Err(e) => failures.push(format!("{} {}: {}", name, label, e)),
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}